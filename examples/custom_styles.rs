@@ -22,6 +22,7 @@ fn main() {
         iced_baseview: IcedBaseviewSettings {
             ignore_non_modifier_keys: false,
             always_redraw: true,
+            ..Default::default()
         },
         flags: (),
         fonts: Default::default(),
@@ -92,6 +93,7 @@ impl Application for MyProgram {
     type Executor = iced_baseview::executor::Default;
     type Message = ();
     type Flags = ();
+    type Output = ();
     type Theme = Theme;
 
     fn new(_flags: ()) -> (Self, Command<Self::Message>) {
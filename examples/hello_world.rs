@@ -21,6 +21,7 @@ fn main() {
         iced_baseview: IcedBaseviewSettings {
             ignore_non_modifier_keys: false,
             always_redraw: true,
+            ..Default::default()
         },
         flags: (),
         fonts: Default::default(),
@@ -36,6 +37,7 @@ impl Application for MyProgram {
     type Message = ();
     type Theme = iced_baseview::style::Theme;
     type Flags = ();
+    type Output = ();
 
     fn new(_flags: ()) -> (Self, Command<Self::Message>) {
         (Self {}, Command::none())
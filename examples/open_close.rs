@@ -0,0 +1,240 @@
+//! Repeatedly opens windows and closes them again after a few frames, and checks that every one of
+//! them is torn down cleanly.
+//!
+//! Standalone windows close themselves, like a tool that is done. Parented windows are opened in a
+//! host window and closed by the host, like a plugin editor. Exits with an error if a window does
+//! not shut down, or leaks its application.
+use iced_baseview::{
+    baseview::{
+        Event, EventStatus, Size, Window, WindowHandler, WindowOpenOptions, WindowScalePolicy,
+    },
+    core::{Element, Length},
+    open_blocking_with_result, open_parented,
+    runtime::{futures::Subscription, Command},
+    settings::IcedBaseviewSettings,
+    widget::Container,
+    widget::Text,
+    window::{self, WindowHandle, WindowSubs},
+    Application, Settings,
+};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How many times each kind of window is opened and closed.
+const ITERATIONS: usize = 50;
+/// How many frames each window is open for.
+const FRAMES: usize = 10;
+/// How long a parented window may take to shut down once the host has closed it.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The number of applications that have been created but not dropped yet.
+static LIVE_APPLICATIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn main() {
+    for iteration in 0..ITERATIONS {
+        let (application, _) = open_blocking_with_result::<MyProgram>(settings(
+            format!("Standalone window {}/{}", iteration + 1, ITERATIONS),
+            Mode::CloseItself,
+        ));
+
+        match application {
+            Some(application) if application.frames >= FRAMES => {}
+            Some(application) => fail(format!(
+                "standalone window {} closed after {} frames instead of {}",
+                iteration + 1,
+                application.frames,
+                FRAMES
+            )),
+            None => fail(format!(
+                "standalone window {} did not return its application",
+                iteration + 1
+            )),
+        }
+
+        check_no_live_applications(&format!("standalone window {}", iteration + 1));
+    }
+
+    println!("Opened and closed {} standalone windows", ITERATIONS);
+
+    Window::open_blocking(
+        WindowOpenOptions {
+            title: String::from("iced_baseview open/close host"),
+            size: Size::new(400.0, 300.0),
+            scale: WindowScalePolicy::SystemScaleFactor,
+        },
+        |_| Host {
+            editor: None,
+            opened: 0,
+            frames: 0,
+            closed_at: None,
+        },
+    );
+
+    println!("Opened and closed {} parented windows", ITERATIONS);
+}
+
+fn settings(title: String, mode: Mode) -> Settings<Mode> {
+    Settings {
+        window: WindowOpenOptions {
+            title,
+            size: Size::new(300.0, 200.0),
+            scale: WindowScalePolicy::SystemScaleFactor,
+        },
+        iced_baseview: IcedBaseviewSettings {
+            always_redraw: true,
+            ..Default::default()
+        },
+        flags: mode,
+        fonts: Default::default(),
+    }
+}
+
+fn check_no_live_applications(window: &str) {
+    let live = LIVE_APPLICATIONS.load(Ordering::SeqCst);
+    if live != 0 {
+        fail(format!(
+            "{} has shut down, but {} application(s) are still alive",
+            window, live
+        ));
+    }
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
+
+/// Opens an editor in its window, closes it after a few frames, and waits for it to shut down
+/// before opening the next one.
+struct Host {
+    editor: Option<WindowHandle<Message, ()>>,
+    opened: usize,
+    frames: usize,
+    closed_at: Option<Instant>,
+}
+
+impl WindowHandler for Host {
+    fn on_frame(&mut self, window: &mut Window<'_>) {
+        let Some(editor) = &mut self.editor else {
+            if self.opened == ITERATIONS {
+                window.close();
+            } else {
+                self.opened += 1;
+                self.frames = 0;
+                self.closed_at = None;
+                self.editor = Some(open_parented::<MyProgram, _>(
+                    &*window,
+                    settings(
+                        format!("Parented window {}/{}", self.opened, ITERATIONS),
+                        Mode::ClosedByHost,
+                    ),
+                ));
+            }
+
+            return;
+        };
+
+        self.frames += 1;
+
+        match self.closed_at {
+            None if self.frames >= FRAMES => {
+                editor.close_window();
+                self.closed_at = Some(Instant::now());
+            }
+            None => {}
+            Some(_) if editor.is_finished() => {
+                check_no_live_applications(&format!("parented window {}", self.opened));
+                self.editor = None;
+            }
+            Some(closed_at) if closed_at.elapsed() > SHUTDOWN_TIMEOUT => fail(format!(
+                "parented window {} did not shut down within {:?}",
+                self.opened, SHUTDOWN_TIMEOUT
+            )),
+            Some(_) => {}
+        }
+    }
+
+    fn on_event(&mut self, _window: &mut Window<'_>, _event: Event) -> EventStatus {
+        EventStatus::Ignored
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    /// The window closes itself after a few frames.
+    CloseItself,
+    /// The window stays open until the host closes it.
+    ClosedByHost,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    OnFrame,
+}
+
+struct MyProgram {
+    mode: Mode,
+    frames: usize,
+}
+
+impl Drop for MyProgram {
+    fn drop(&mut self) {
+        let _ = LIVE_APPLICATIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Application for MyProgram {
+    type Executor = iced_baseview::executor::Default;
+    type Message = Message;
+    type Flags = Mode;
+    type Output = ();
+    type Theme = iced_baseview::style::Theme;
+
+    fn new(mode: Mode) -> (Self, Command<Self::Message>) {
+        let _ = LIVE_APPLICATIONS.fetch_add(1, Ordering::SeqCst);
+
+        (Self { mode, frames: 0 }, Command::none())
+    }
+
+    fn subscription(&self, window_subs: &mut WindowSubs<Message>) -> Subscription<Message> {
+        window_subs.on_frame = Some(Arc::new(|| Some(Message::OnFrame)));
+        Subscription::none()
+    }
+
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        match message {
+            Message::OnFrame => {
+                self.frames += 1;
+
+                if let Mode::CloseItself = self.mode {
+                    if self.frames == FRAMES {
+                        return window::close();
+                    }
+                }
+            }
+        }
+
+        Command::none()
+    }
+
+    fn view(
+        &self,
+    ) -> Element<'_, Self::Message, iced_baseview::widget::renderer::Renderer<Self::Theme>> {
+        Container::new(Text::new(format!("Frame {}", self.frames)))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    fn title(&self) -> String {
+        "Open/close".into()
+    }
+
+    fn theme(&self) -> Self::Theme {
+        Default::default()
+    }
+}
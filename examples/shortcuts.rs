@@ -0,0 +1,120 @@
+use iced_baseview::{
+    baseview::{Size, WindowOpenOptions, WindowScalePolicy},
+    core::{Alignment, Element, Length},
+    keyboard_types::{Key, Modifiers},
+    open_blocking,
+    runtime::Command,
+    settings::IcedBaseviewSettings,
+    shortcut::Shortcut,
+    widget::Column,
+    widget::Container,
+    widget::Text,
+    Application, Settings,
+};
+
+fn main() {
+    let settings = Settings {
+        window: WindowOpenOptions {
+            title: String::from("iced_baseview shortcuts"),
+            size: Size::new(500.0, 300.0),
+            scale: WindowScalePolicy::SystemScaleFactor,
+        },
+        iced_baseview: IcedBaseviewSettings::default(),
+        flags: (),
+        fonts: Default::default(),
+    };
+
+    open_blocking::<MyProgram>(settings);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    Increment,
+    Decrement,
+    Undo,
+    Redo,
+}
+
+#[derive(Default)]
+struct MyProgram {
+    history: Vec<i32>,
+    undone: Vec<i32>,
+    value: i32,
+}
+
+impl MyProgram {
+    fn set_value(&mut self, value: i32) {
+        self.history.push(self.value);
+        self.undone.clear();
+        self.value = value;
+    }
+}
+
+impl Application for MyProgram {
+    type Executor = iced_baseview::executor::Default;
+    type Message = Message;
+    type Theme = iced_baseview::style::Theme;
+    type Flags = ();
+    type Output = ();
+
+    fn new(_flags: ()) -> (Self, Command<Self::Message>) {
+        (Self::default(), Command::none())
+    }
+
+    fn title(&self) -> String {
+        "Shortcuts".into()
+    }
+
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        match message {
+            Message::Increment => self.set_value(self.value + 1),
+            Message::Decrement => self.set_value(self.value - 1),
+            Message::Undo => {
+                if let Some(value) = self.history.pop() {
+                    self.undone.push(self.value);
+                    self.value = value;
+                }
+            }
+            Message::Redo => {
+                if let Some(value) = self.undone.pop() {
+                    self.history.push(self.value);
+                    self.value = value;
+                }
+            }
+        }
+
+        Command::none()
+    }
+
+    fn shortcuts(&self) -> Vec<Shortcut<Self::Message>> {
+        vec![
+            // "+" needs Shift on some layouts, which these shortcuts don't mind
+            Shortcut::character(Modifiers::empty(), "+", Message::Increment),
+            Shortcut::character(Modifiers::empty(), "-", Message::Decrement),
+            Shortcut::new(Modifiers::empty(), Key::ArrowUp, Message::Increment),
+            Shortcut::new(Modifiers::empty(), Key::ArrowDown, Message::Decrement),
+            // Ctrl+Z follows the Z key, wherever it is on the keyboard
+            Shortcut::character(Modifiers::CONTROL, "z", Message::Undo),
+            Shortcut::character(Modifiers::CONTROL | Modifiers::SHIFT, "z", Message::Redo),
+        ]
+    }
+
+    fn view(
+        &self,
+    ) -> Element<'_, Self::Message, iced_baseview::widget::renderer::Renderer<Self::Theme>> {
+        let content = Column::new()
+            .width(Length::Fill)
+            .align_items(Alignment::Center)
+            .push(Text::new(format!("{}", self.value)))
+            .push(Text::new(
+                "+/- to change the value, Ctrl+Z and Ctrl+Shift+Z to undo and redo",
+            ));
+
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+}
@@ -0,0 +1,134 @@
+use iced_baseview::{
+    baseview::{Size, WindowOpenOptions, WindowScalePolicy},
+    core::{Alignment, Element, Length},
+    open_blocking,
+    runtime::{futures::Subscription, Command},
+    settings::IcedBaseviewSettings,
+    time,
+    widget::Button,
+    widget::Column,
+    widget::Container,
+    widget::Text,
+    window::WindowSubs,
+    Application, Settings,
+};
+use std::time::{Duration, Instant};
+
+fn main() {
+    let settings = Settings {
+        window: WindowOpenOptions {
+            title: String::from("iced_baseview timer"),
+            size: Size::new(500.0, 300.0),
+            scale: WindowScalePolicy::SystemScaleFactor,
+        },
+        iced_baseview: IcedBaseviewSettings {
+            ignore_non_modifier_keys: false,
+            // The timer subscription wakes up the window, so there is no need to redraw every frame
+            always_redraw: false,
+            ..Default::default()
+        },
+        flags: (),
+        fonts: Default::default(),
+    };
+
+    open_blocking::<Timer>(settings);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    Tick(Instant),
+    Toggle,
+    Reset,
+}
+
+struct Timer {
+    elapsed: Duration,
+    last_tick: Option<Instant>,
+    running: bool,
+}
+
+impl Application for Timer {
+    type Executor = iced_baseview::executor::Default;
+    type Message = Message;
+    type Flags = ();
+    type Output = ();
+    type Theme = iced_baseview::style::Theme;
+
+    fn new(_flags: ()) -> (Self, Command<Self::Message>) {
+        (
+            Self {
+                elapsed: Duration::ZERO,
+                last_tick: None,
+                running: true,
+            },
+            Command::none(),
+        )
+    }
+
+    fn subscription(&self, _window_subs: &mut WindowSubs<Message>) -> Subscription<Message> {
+        if self.running {
+            time::every(Duration::from_millis(10)).map(Message::Tick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        match message {
+            Message::Tick(now) => {
+                if let Some(last_tick) = self.last_tick {
+                    self.elapsed += now - last_tick;
+                }
+                self.last_tick = Some(now);
+            }
+            Message::Toggle => {
+                self.running = !self.running;
+                self.last_tick = None;
+            }
+            Message::Reset => {
+                self.elapsed = Duration::ZERO;
+                self.last_tick = None;
+            }
+        }
+
+        Command::none()
+    }
+
+    fn view(
+        &self,
+    ) -> Element<'_, Self::Message, iced_baseview::widget::renderer::Renderer<Self::Theme>> {
+        let seconds = self.elapsed.as_secs();
+        let centiseconds = self.elapsed.subsec_millis() / 10;
+
+        let content = Column::new()
+            .width(Length::Fill)
+            .align_items(Alignment::Center)
+            .spacing(20)
+            .push(Text::new(format!(
+                "{:02}:{:02}.{:02}",
+                seconds / 60,
+                seconds % 60,
+                centiseconds
+            )))
+            .push(
+                Button::new(Text::new(if self.running { "Stop" } else { "Start" }))
+                    .on_press(Message::Toggle),
+            )
+            .push(Button::new(Text::new("Reset")).on_press(Message::Reset));
+
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+
+    fn title(&self) -> String {
+        "Timer".into()
+    }
+
+    fn theme(&self) -> Self::Theme {
+        Default::default()
+    }
+}
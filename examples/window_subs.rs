@@ -8,11 +8,11 @@ use iced_baseview::{
     widget::Column,
     widget::Container,
     widget::Text,
-    window::WindowSubs,
+    window::{FrameInfo, WindowSubs},
     Application, Settings,
 };
 use iced_runtime::window::Action;
-use std::{sync::Arc, time::{Duration, Instant}};
+use std::{sync::Arc, time::Duration};
 
 static COUNT_INTERVAL: Duration = Duration::from_millis(1000);
 
@@ -26,6 +26,7 @@ fn main() {
         iced_baseview: IcedBaseviewSettings {
             ignore_non_modifier_keys: false,
             always_redraw: true,
+            ..Default::default()
         },
         flags: (),
         fonts: Default::default(),
@@ -36,13 +37,13 @@ fn main() {
 
 #[derive(Debug, Clone, Copy)]
 enum Message {
-    OnFrame,
+    OnFrame(FrameInfo),
     WillClose,
     CloseWindow,
 }
 
 struct MyProgram {
-    next_interval: Instant,
+    elapsed: Duration,
     count: usize,
 }
 
@@ -50,12 +51,13 @@ impl Application for MyProgram {
     type Executor = iced_baseview::executor::Default;
     type Message = Message;
     type Flags = ();
+    type Output = ();
     type Theme = iced_baseview::style::Theme;
 
     fn new(_flags: ()) -> (Self, Command<Self::Message>) {
         (
             Self {
-                next_interval: Instant::now() + COUNT_INTERVAL,
+                elapsed: Duration::ZERO,
                 count: 0,
             },
             Command::none(),
@@ -63,17 +65,17 @@ impl Application for MyProgram {
     }
 
     fn subscription(&self, window_subs: &mut WindowSubs<Message>) -> Subscription<Message> {
-        window_subs.on_frame = Some(Arc::new(|| Some(Message::OnFrame)));
+        window_subs.on_frame_info = Some(Arc::new(|frame| Some(Message::OnFrame(frame))));
         window_subs.on_window_will_close = Some(Arc::new(|| Some(Message::WillClose)));
         Subscription::none()
     }
 
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
         match message {
-            Message::OnFrame => {
-                let now = Instant::now();
-                while now >= self.next_interval {
-                    self.next_interval += COUNT_INTERVAL;
+            Message::OnFrame(frame) => {
+                self.elapsed += frame.delta;
+                while self.elapsed >= COUNT_INTERVAL {
+                    self.elapsed -= COUNT_INTERVAL;
                     self.count += 1;
                 }
             }
@@ -11,6 +11,8 @@ use crate::core::mouse;
 use crate::core::renderer;
 use crate::core::widget::operation;
 use crate::core::Size;
+use crate::core::{Point, Rectangle};
+use crate::executor::{Tracked, TrackedTasks};
 use crate::futures::futures;
 use crate::futures::{Executor, Runtime, Subscription};
 use crate::graphics::compositor::{self, Compositor};
@@ -18,15 +20,22 @@ use crate::runtime::clipboard;
 use crate::runtime::program::Program;
 use crate::runtime::user_interface::{self, UserInterface};
 use crate::runtime::{Command, Debug};
+use crate::settings::PanicHook;
 use crate::style::application::{Appearance, StyleSheet};
-use crate::window::{IcedWindow, RuntimeEvent, WindowQueue, WindowSubs};
+use crate::window::{
+    Completion, Composition, FrameInfo, IcedWindow, RuntimeEvent, RuntimeWaker, WindowContext,
+    WindowQueue, WindowRequest, WindowShared, WindowSubs,
+};
 use crate::{Clipboard, Error, Proxy, Settings};
 
 use futures::channel::mpsc;
 
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::mem::ManuallyDrop;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "trace")]
 pub use profiler::Profiler;
@@ -51,6 +60,9 @@ where
     /// The data needed to initialize your [`Application`].
     type Flags;
 
+    /// The type of outputs your [`Application`] emits to the owner of its window.
+    type Output: Send + 'static;
+
     /// Initializes the [`Application`] with the flags provided to
     /// [`run`] as part of the [`Settings`].
     ///
@@ -107,6 +119,37 @@ where
         baseview::WindowScalePolicy::SystemScaleFactor
     }
 
+    /// Returns the logical size the user interface is designed at, if it should be scaled to fit
+    /// the window.
+    ///
+    /// By default, it returns `None`.
+    fn scale_to_fit(&self) -> Option<baseview::Size> {
+        None
+    }
+
+    /// Returns a future to run on the cleanup thread when the window closes, after all commands
+    /// and subscriptions have been cancelled.
+    ///
+    /// By default, it returns `None`.
+    fn cleanup(&mut self) -> Option<futures::future::BoxFuture<'static, ()>> {
+        None
+    }
+
+    /// Returns the keyboard shortcuts of the application in its current state.
+    ///
+    /// By default, it returns no shortcuts.
+    fn shortcuts(&self) -> Vec<crate::shortcut::Shortcut<Self::Message>> {
+        Vec::new()
+    }
+
+    /// Returns the message of a panic the application has caught in its own code, if it has not
+    /// been returned before.
+    ///
+    /// By default, it returns `None`.
+    fn take_panic(&self) -> Option<String> {
+        None
+    }
+
     fn renderer_settings() -> crate::renderer::Settings;
 }
 
@@ -118,6 +161,7 @@ pub fn run<A, E, C>(
     // compositor_settings: C::Settings,
     event_sender: mpsc::UnboundedSender<RuntimeEvent<A::Message>>,
     event_receiver: mpsc::UnboundedReceiver<RuntimeEvent<A::Message>>,
+    shared: Arc<WindowShared<A::Output>>,
 ) -> Result<IcedWindow<A>, Error>
 where
     A: Application + 'static + Send,
@@ -125,8 +169,6 @@ where
     C: Compositor<Renderer = A::Renderer, Settings = crate::renderer::Settings> + 'static,
     <A::Renderer as core::Renderer>::Theme: StyleSheet,
 {
-    use futures::task;
-
     #[cfg(feature = "trace")]
     let _guard = Profiler::init();
 
@@ -136,42 +178,56 @@ where
     #[cfg(feature = "trace")]
     let _ = info_span!("Application", "RUN").entered();
 
-    let viewport = {
-        // Assume scale for now until there is an event with a new one.
-        let scale = match settings.window.scale {
-            baseview::WindowScalePolicy::ScaleFactor(scale) => scale,
-            baseview::WindowScalePolicy::SystemScaleFactor => 1.0,
-        };
-
-        let physical_size = Size::new(
-            (settings.window.size.width * scale) as u32,
-            (settings.window.size.height * scale) as u32,
-        );
-
-        iced_graphics::Viewport::with_physical_size(physical_size, scale)
+    // The scale baseview created the window with. Should this not be known yet, assume a scale of
+    // `1.0` until there is an event with a new one.
+    let system_scale_factor = match settings.window.scale {
+        baseview::WindowScalePolicy::ScaleFactor(scale) => scale,
+        baseview::WindowScalePolicy::SystemScaleFactor => {
+            crate::dpi::system_scale_factor(window, settings.window.size).unwrap_or(1.0)
+        }
     };
 
+    let physical_size = Size::new(
+        (settings.window.size.width * system_scale_factor).round() as u32,
+        (settings.window.size.height * system_scale_factor).round() as u32,
+    );
+
     let (runtime_tx, runtime_rx) = mpsc::unbounded::<A::Message>();
 
-    let runtime = {
+    // The window only finishes once both the window itself and all of its futures are gone
+    let completion = shared.completion.hold();
+
+    let (runtime, tasks) = {
         let proxy = Proxy::new(runtime_tx);
-        let executor = E::new().map_err(Error::ExecutorCreationFailed)?;
+        let executor = Tracked::<E>::new().map_err(Error::ExecutorCreationFailed)?;
+        let tasks = executor.tasks();
+        tasks.hold_completion(shared.completion.hold());
 
-        Runtime::new(executor, proxy)
+        (Runtime::new(executor, proxy), tasks)
     };
 
+    let panic_hook = settings.iced_baseview.panic_hook.clone();
+    let manual_pump = settings.iced_baseview.manual_pump;
+
+    let (window_queue, window_queue_rx) = WindowQueue::new();
+
     let (application, init_command) = {
         let flags = settings.flags;
 
-        runtime.enter(|| A::new(flags))
+        window_queue
+            .context(&shared.outputs, &tasks)
+            .enter(|| runtime.enter(|| A::new(flags)))
     };
 
+    let state = State::new(&application, physical_size, system_scale_factor);
+    share_window_info(&state, &shared.window_info);
+
     let compositor_settings = A::renderer_settings();
     let (mut compositor, mut renderer) = C::new(compositor_settings, Some(window))?;
     let surface = compositor.create_surface(
         window,
-        viewport.physical_width(),
-        viewport.physical_height(),
+        state.physical_size().width,
+        state.physical_size().height,
     );
 
     for font in settings.fonts {
@@ -180,25 +236,26 @@ where
         renderer.load_font(font);
     }
 
-    let (window_queue, window_queue_rx) = WindowQueue::new();
     let event_status = Rc::new(RefCell::new(baseview::EventStatus::Ignored));
 
-    let state = State::new(&application, viewport);
-
     let instance = Box::pin({
         let run_instance = run_instance::<A, E, C>(
             application,
-            compositor,
-            renderer,
+            Graphics {
+                surface,
+                renderer,
+                compositor,
+            },
             runtime,
+            tasks,
             debug,
             event_receiver,
             init_command,
             settings.iced_baseview,
-            surface,
             event_status.clone(),
             state,
             window_queue,
+            shared.clone(),
         );
 
         #[cfg(feature = "trace")]
@@ -207,35 +264,54 @@ where
         run_instance
     });
 
-    let runtime_context = task::Context::from_waker(task::noop_waker_ref());
+    let waker = RuntimeWaker::new();
+    crate::executor::register_local(&waker);
 
     Ok(IcedWindow {
         sender: event_sender,
         instance,
-        runtime_context,
+        waker,
+        instance_waker: RuntimeWaker::new(),
+        panic_hook,
+        manual_pump,
         runtime_rx,
         window_queue_rx,
         event_status,
+        exit_state: None,
+        shared,
+        completion,
 
         processed_close_signal: false,
     })
 }
 
+/// The graphics of a window.
+///
+/// The fields are dropped in order, so the surface and the renderer are released before the
+/// compositor that created them. This also holds when a panic unwinds through the instance, which
+/// drops the graphics while the window is still alive.
+struct Graphics<C: Compositor> {
+    surface: C::Surface,
+    renderer: C::Renderer,
+    compositor: C,
+}
+
 async fn run_instance<A, E, C>(
     mut application: A,
-    mut compositor: C,
-    mut renderer: A::Renderer,
-    mut runtime: Runtime<E, Proxy<A::Message>, A::Message>,
+    mut graphics: Graphics<C>,
+    mut runtime: Runtime<Tracked<E>, Proxy<A::Message>, A::Message>,
+    tasks: TrackedTasks,
     mut debug: Debug,
     mut event_receiver: mpsc::UnboundedReceiver<RuntimeEvent<A::Message>>,
     init_command: Command<A::Message>,
 
     settings: crate::settings::IcedBaseviewSettings,
-    mut surface: C::Surface,
     event_status: Rc<RefCell<baseview::EventStatus>>,
     mut state: State<A>,
     mut window_queue: WindowQueue,
-) where
+    shared: Arc<WindowShared<A::Output>>,
+) -> A
+where
     A: Application + 'static,
     E: Executor + 'static,
     C: Compositor<Renderer = A::Renderer> + 'static,
@@ -244,6 +320,7 @@ async fn run_instance<A, E, C>(
     use futures::stream::StreamExt;
 
     let mut viewport_version = state.viewport_version();
+    let window_context = window_queue.context(&shared.outputs, &tasks);
 
     let mut clipboard = Clipboard::new();
     let mut cache = user_interface::Cache::default();
@@ -253,7 +330,7 @@ async fn run_instance<A, E, C>(
         &application,
         &mut cache,
         &state,
-        &mut renderer,
+        &mut graphics.renderer,
         init_command,
         &mut runtime,
         &mut clipboard,
@@ -265,8 +342,8 @@ async fn run_instance<A, E, C>(
     let mut user_interface = ManuallyDrop::new(build_user_interface(
         &application,
         cache,
-        &mut renderer,
-        state.logical_size(),
+        &mut graphics.renderer,
+        state.layout_size(),
         &mut debug,
     ));
 
@@ -280,18 +357,71 @@ async fn run_instance<A, E, C>(
     // frame
     let mut needs_update = true;
     let mut did_process_event = false;
+    let mut presented_first_frame = false;
+
+    // Timestamps the frames for `WindowSubs::on_frame_info`
+    let mut last_frame_time: Option<Instant> = None;
+    let mut frame_index: u64 = 0;
+    let mut presented_last_frame = false;
+
+    // Events produced by the runtime itself, these are processed before any new events
+    let mut pending_events = VecDeque::new();
+
+    messages.extend(process_window_requests(
+        &mut state,
+        &mut window_queue,
+        &window_subs,
+        &settings,
+        &shared.ime_cursor_area,
+        &mut debug,
+        &mut pending_events,
+    ));
 
     debug.startup_finished();
 
-    while let Some(event) = event_receiver.next().await {
+    loop {
+        report_panic(&application, settings.panic_hook.as_ref());
+
+        let event = match pending_events.pop_front() {
+            Some(event) => event,
+            None => match event_receiver.next().await {
+                Some(event) => event,
+                None => break,
+            },
+        };
+
         match event {
             RuntimeEvent::MainEventsCleared => {
+                // Frames are only processed while the window is dirty, so ask for the next one if
+                // the application wants to be updated or redrawn continuously. This does not
+                // depend on whether the frame subscriptions produce a message this time.
+                if wants_every_frame(&settings, &window_subs) {
+                    request_frame().await;
+                }
+
+                let now = Instant::now();
+                let frame_info = FrameInfo {
+                    now,
+                    delta: last_frame_time.map_or(Duration::ZERO, |last| now - last),
+                    frame_index,
+                    presented_last_frame,
+                };
+                last_frame_time = Some(now);
+                frame_index = frame_index.wrapping_add(1);
+                presented_last_frame = false;
+
                 if let Some(message) = &window_subs.on_frame {
                     if let Some(message) = message() {
                         messages.push(message);
                     }
                 }
 
+                if let Some(message) = &window_subs.on_frame_info {
+                    if let Some(message) = message(frame_info) {
+                        messages.push(message);
+                    }
+                }
+
                 if !did_process_event
                     && events.is_empty()
                     && messages.is_empty()
@@ -307,7 +437,7 @@ async fn run_instance<A, E, C>(
                     let (interface_state, statuses) = user_interface.update(
                         &events,
                         state.cursor(),
-                        &mut renderer,
+                        &mut graphics.renderer,
                         &mut clipboard,
                         &mut messages,
                     );
@@ -334,8 +464,9 @@ async fn run_instance<A, E, C>(
                         &mut application,
                         &mut cache,
                         &state,
-                        &mut renderer,
+                        &mut graphics.renderer,
                         &mut runtime,
+                        &window_context,
                         &mut clipboard,
                         &mut debug,
                         &mut messages,
@@ -346,13 +477,28 @@ async fn run_instance<A, E, C>(
                     // Update window
                     state.synchronize(&application);
 
+                    // The application may have subscribed to frames just now
+                    if wants_every_frame(&settings, &window_subs) {
+                        request_frame().await;
+                    }
+
+                    messages.extend(process_window_requests(
+                        &mut state,
+                        &mut window_queue,
+                        &window_subs,
+                        &settings,
+                        &shared.ime_cursor_area,
+                        &mut debug,
+                        &mut pending_events,
+                    ));
+
                     let should_exit = false; // FIXME
 
                     user_interface = ManuallyDrop::new(build_user_interface(
                         &application,
                         cache,
-                        &mut renderer,
-                        state.logical_size(),
+                        &mut graphics.renderer,
+                        state.layout_size(),
                         &mut debug,
                     ));
 
@@ -362,14 +508,8 @@ async fn run_instance<A, E, C>(
                 }
 
                 debug.draw_started();
-                let new_mouse_interaction = user_interface.draw(
-                    &mut renderer,
-                    state.theme(),
-                    &iced_runtime::core::renderer::Style {
-                        text_color: state.text_color(),
-                    },
-                    state.cursor(),
-                );
+                let new_mouse_interaction =
+                    draw(&mut user_interface, &mut graphics.renderer, &state);
                 debug.draw_finished();
 
                 if new_mouse_interaction != mouse_interaction {
@@ -386,6 +526,21 @@ async fn run_instance<A, E, C>(
             RuntimeEvent::UserEvent(message) => {
                 messages.push(message);
             }
+            RuntimeEvent::Composition(composition) => {
+                if let Some(message) = &window_subs.on_composition {
+                    messages.extend(message(&composition));
+                }
+
+                // Committed text is typed into the focused widget like regular characters. The
+                // events are handled with the next frame.
+                if let Composition::Committed(text) = composition {
+                    events.extend(text.chars().map(|chr| {
+                        core::Event::Keyboard(core::keyboard::Event::CharacterReceived(chr))
+                    }));
+                }
+
+                did_process_event = true;
+            }
             RuntimeEvent::RedrawRequested => {
                 #[cfg(feature = "trace")]
                 let _ = info_span!("Application", "FRAME").entered();
@@ -406,24 +561,18 @@ async fn run_instance<A, E, C>(
                 let current_viewport_version = state.viewport_version();
 
                 if viewport_version != current_viewport_version {
-                    let logical_size = state.logical_size();
+                    let layout_size = state.layout_size();
 
                     debug.layout_started();
                     user_interface = ManuallyDrop::new(
                         ManuallyDrop::into_inner(user_interface)
-                            .relayout(logical_size, &mut renderer),
+                            .relayout(layout_size, &mut graphics.renderer),
                     );
                     debug.layout_finished();
 
                     debug.draw_started();
-                    let new_mouse_interaction = user_interface.draw(
-                        &mut renderer,
-                        state.theme(),
-                        &renderer::Style {
-                            text_color: state.text_color(),
-                        },
-                        state.cursor(),
-                    );
+                    let new_mouse_interaction =
+                        draw(&mut user_interface, &mut graphics.renderer, &state);
 
                     if new_mouse_interaction != mouse_interaction {
                         // window.set_cursor_icon(conversion::mouse_interaction(
@@ -434,8 +583,8 @@ async fn run_instance<A, E, C>(
                     }
                     debug.draw_finished();
 
-                    compositor.configure_surface(
-                        &mut surface,
+                    graphics.compositor.configure_surface(
+                        &mut graphics.surface,
                         physical_size.width,
                         physical_size.height,
                     );
@@ -443,15 +592,29 @@ async fn run_instance<A, E, C>(
                     viewport_version = current_viewport_version;
                 }
 
-                match compositor.present(
-                    &mut renderer,
-                    &mut surface,
+                match graphics.compositor.present(
+                    &mut graphics.renderer,
+                    &mut graphics.surface,
                     state.viewport(),
                     state.background_color(),
                     &debug.overlay(),
                 ) {
                     Ok(()) => {
                         debug.render_finished();
+                        presented_last_frame = true;
+
+                        if !presented_first_frame {
+                            presented_first_frame = true;
+
+                            if let Some(message) = &window_subs.on_first_frame_presented {
+                                if let Some(message) = message() {
+                                    messages.push(message);
+
+                                    // Make sure the message gets handled on the next frame
+                                    request_frame().await;
+                                }
+                            }
+                        }
 
                         // TODO: Handle animations!
                         // Maybe we can use `ControlFlow::WaitUntil` for this.
@@ -465,62 +628,193 @@ async fn run_instance<A, E, C>(
                             debug.render_finished();
 
                             redraw_requested = true;
+                            request_frame().await;
                         }
                     },
                 }
             }
+            // Resize requests from the host are turned into regular resize events, so they go
+            // through the same path as resizes reported by baseview.
+            RuntimeEvent::Resize(size) => {
+                let size = settings
+                    .size_constraints
+                    .check_size(size, state.window_info().logical_size());
+
+                pending_events.push_front(host_resize(&state, &mut window_queue, &mut debug, size));
+            }
+            RuntimeEvent::SetScaleFactor(scale_factor) => {
+                let previous_scale_factor = state.scale_factor();
+                let size = state.window_info().logical_size();
+                state.set_host_scale_factor(scale_factor);
+
+                // The resize event no longer sees a change, so it is reported here
+                if state.scale_factor() != previous_scale_factor {
+                    if let Some(message) = &window_subs.on_scale_factor_changed {
+                        messages.extend(message(state.scale_factor()));
+                    }
+                }
+
+                pending_events.push_front(host_resize(&state, &mut window_queue, &mut debug, size));
+            }
             RuntimeEvent::Baseview((event, do_send_status)) => {
+                let previous_size = state.window_info().logical_size();
+                let previous_scale_factor = state.scale_factor();
+
                 state.update(&event, &mut debug);
 
+                window_event_messages(
+                    &window_subs,
+                    &event,
+                    &state,
+                    previous_scale_factor,
+                    &mut messages,
+                );
+
+                let mut subs_status = EventStatus::Ignored;
+                if let Some(on_baseview_event) = &window_subs.on_baseview_event {
+                    let (message, status) = on_baseview_event(&event);
+                    messages.extend(message);
+                    subs_status = status;
+                }
+
+                if let baseview::Event::Keyboard(key_event) = &event {
+                    if settings
+                        .exit_shortcuts
+                        .iter()
+                        .any(|shortcut| shortcut.matches(key_event))
+                    {
+                        // The application may veto the close request, and close the window itself
+                        // later on
+                        match &window_subs.on_close_requested {
+                            Some(message) => {
+                                if let Some(message) = message() {
+                                    messages.push(message);
+                                }
+                            }
+                            None => {
+                                if window_queue.close_window().is_err() {
+                                    debug.log_message(
+                                        &"could not send close_window command".to_string(),
+                                    );
+                                }
+                            }
+                        }
+
+                        if do_send_status {
+                            *event_status.borrow_mut() = EventStatus::Captured;
+                        }
+                        did_process_event = true;
+                        continue;
+                    }
+                }
+
+                // Shortcuts only apply once the widgets have left the key press alone, see below
+                let zoom_request = if settings.zoom_shortcuts && state.is_zoomable() {
+                    WindowRequest::from_zoom_shortcut(&event)
+                } else {
+                    None
+                };
+                let shortcut_message = match &event {
+                    baseview::Event::Keyboard(key_event)
+                        if key_event.state == keyboard_types::KeyState::Down =>
+                    {
+                        crate::shortcut::find_message(application.shortcuts(), key_event)
+                    }
+                    _ => None,
+                };
+
+                if let baseview::Event::Window(baseview::WindowEvent::Resized(_)) = &event {
+                    share_window_info(&state, &shared.window_info);
+
+                    // Resizes reported by baseview come from the user or the windowing system, so
+                    // the window may need to be brought back within its size constraints.
+                    if do_send_status {
+                        let size = state.window_info().logical_size();
+                        let constrained_size =
+                            settings.size_constraints.check_size(size, previous_size);
+
+                        if (constrained_size.width - size.width).abs() >= 1.0
+                            || (constrained_size.height - size.height).abs() >= 1.0
+                        {
+                            let window_info = state.resized_window_info(constrained_size);
+
+                            if window_queue.resize(window_info.logical_size()).is_err() {
+                                debug.log_message(&"could not send resize command".to_string());
+                            }
+                        }
+                    }
+                }
+
                 let ignore_non_modifier_keys = application
                     .ignore_non_modifier_keys()
                     .unwrap_or(settings.ignore_non_modifier_keys);
 
+                let cursor_mapping = state.cursor_mapping();
+
                 crate::conversion::baseview_to_iced_events(
                     event,
                     &mut events,
                     state.modifiers_mut(),
                     ignore_non_modifier_keys,
+                    cursor_mapping,
                 );
 
-                if events.is_empty() {
-                    if do_send_status {
-                        *event_status.borrow_mut() = EventStatus::Ignored;
-                    }
-                    continue;
-                }
+                let captured = if events.is_empty() {
+                    false
+                } else {
+                    debug.event_processing_started();
+                    let (interface_state, statuses) = user_interface.update(
+                        &events,
+                        state.cursor(),
+                        &mut graphics.renderer,
+                        &mut clipboard,
+                        &mut messages,
+                    );
+                    // Will trigger an update when the next frame gets drawn
+                    needs_update |= matches!(interface_state, user_interface::State::Outdated,);
 
-                debug.event_processing_started();
+                    let captured = statuses
+                        .iter()
+                        .any(|status| matches!(status, crate::core::event::Status::Captured));
 
-                let (interface_state, statuses) = user_interface.update(
-                    &events,
-                    state.cursor(),
-                    &mut renderer,
-                    &mut clipboard,
-                    &mut messages,
-                );
-                // Will trigger an update when the next frame gets drawn
-                needs_update |= matches!(interface_state, user_interface::State::Outdated,);
+                    debug.event_processing_finished();
 
-                if do_send_status {
-                    let mut final_status = EventStatus::Ignored;
-                    for status in &statuses {
-                        if let crate::core::event::Status::Captured = status {
-                            final_status = EventStatus::Captured;
-                            break;
-                        }
+                    for (event, status) in events.drain(..).zip(statuses.into_iter()) {
+                        runtime.broadcast(event, status);
                     }
 
-                    *event_status.borrow_mut() = final_status;
-                }
+                    did_process_event = true;
+                    captured
+                };
+
+                // A focused text input keeps the keys typed into it, even if they are shortcuts
+                let mut final_status = subs_status;
+                if captured {
+                    final_status = EventStatus::Captured;
+                } else if let Some(request) = zoom_request {
+                    window_queue.request(request);
+                    messages.extend(process_window_requests(
+                        &mut state,
+                        &mut window_queue,
+                        &window_subs,
+                        &settings,
+                        &shared.ime_cursor_area,
+                        &mut debug,
+                        &mut pending_events,
+                    ));
 
-                debug.event_processing_finished();
+                    final_status = EventStatus::Captured;
+                    did_process_event = true;
+                } else if let Some(message) = shortcut_message {
+                    messages.push(message);
 
-                for (event, status) in events.drain(..).zip(statuses.into_iter()) {
-                    runtime.broadcast(event, status);
+                    final_status = EventStatus::Captured;
+                    did_process_event = true;
                 }
 
-                did_process_event = true;
+                if do_send_status {
+                    *event_status.borrow_mut() = final_status;
+                }
             }
             RuntimeEvent::WillClose => {
                 if let Some(message) = &window_subs.on_window_will_close {
@@ -535,8 +829,9 @@ async fn run_instance<A, E, C>(
                         &mut application,
                         &mut cache,
                         &state,
-                        &mut renderer,
+                        &mut graphics.renderer,
                         &mut runtime,
+                        &window_context,
                         &mut clipboard,
                         &mut debug,
                         &mut messages,
@@ -550,8 +845,8 @@ async fn run_instance<A, E, C>(
                     user_interface = ManuallyDrop::new(build_user_interface(
                         &mut application,
                         cache,
-                        &mut renderer,
-                        state.logical_size(),
+                        &mut graphics.renderer,
+                        state.layout_size(),
                         &mut debug,
                     ));
                 }
@@ -561,8 +856,246 @@ async fn run_instance<A, E, C>(
         }
     }
 
-    // Manually drop the user interface
+    // The user interface borrows the application, so it goes first. This also releases the
+    // resources it holds on to from the renderer.
     drop(ManuallyDrop::into_inner(user_interface));
+
+    report_panic(&application, settings.panic_hook.as_ref());
+
+    shutdown(
+        &mut application,
+        &mut runtime,
+        &tasks,
+        &shared.completion,
+        settings.cleanup_timeout,
+    );
+
+    // Tear down the graphics while the baseview window is still alive. This happens while handling
+    // `WillClose`, before baseview invalidates the window handle.
+    drop(graphics);
+
+    application
+}
+
+/// Logs a panic the [`Application`] has caught in its own code, and passes it on to the panic
+/// hook.
+fn report_panic<A: Application>(application: &A, panic_hook: Option<&PanicHook>) {
+    if let Some(message) = application.take_panic() {
+        log::error!("the application panicked: {}", message);
+
+        if let Some(panic_hook) = panic_hook {
+            panic_hook(&message);
+        }
+    }
+}
+
+/// Cancels all commands and subscriptions of the [`Application`], and then hands its cleanup
+/// future to the cleanup thread, which runs it for at most `timeout`. The window does not finish
+/// before the cleanup future has completed or has been dropped.
+fn shutdown<A, E>(
+    application: &mut A,
+    runtime: &mut Runtime<Tracked<E>, Proxy<A::Message>, A::Message>,
+    tasks: &TrackedTasks,
+    completion: &Arc<Completion>,
+    timeout: Duration,
+) where
+    A: Application,
+    E: Executor,
+    <A::Renderer as core::Renderer>::Theme: StyleSheet,
+{
+    runtime.track(Subscription::none().into_recipes());
+    tasks.abort_all();
+
+    // Created within the executor, so the future can pick up what it needs from its context
+    if let Some(cleanup) = runtime.enter(|| application.cleanup()) {
+        let hold = completion.hold();
+
+        crate::cleanup::spawn(
+            Box::pin(async move {
+                cleanup.await;
+                drop(hold);
+            }),
+            timeout,
+        );
+    }
+}
+
+/// Resizes the window on behalf of the host, returning the resize event the window should process
+/// in the meantime.
+fn host_resize<A: Application + 'static>(
+    state: &State<A>,
+    window_queue: &mut WindowQueue,
+    debug: &mut Debug,
+    size: baseview::Size,
+) -> RuntimeEvent<A::Message>
+where
+    <A::Renderer as core::Renderer>::Theme: StyleSheet,
+{
+    let window_info = state.resized_window_info(size);
+
+    if window_queue.resize(window_info.logical_size()).is_err() {
+        debug.log_message(&"could not send resize command".to_string());
+    }
+
+    RuntimeEvent::Baseview((
+        baseview::Event::Window(baseview::WindowEvent::Resized(window_info)),
+        false,
+    ))
+}
+
+/// Adds the messages the application wants to receive for a window event through its
+/// [`WindowSubs`].
+fn window_event_messages<A: Application>(
+    window_subs: &WindowSubs<A::Message>,
+    event: &baseview::Event,
+    state: &State<A>,
+    previous_scale_factor: f64,
+    messages: &mut Vec<A::Message>,
+) where
+    <A::Renderer as core::Renderer>::Theme: StyleSheet,
+{
+    if let baseview::Event::Window(baseview::WindowEvent::Resized(_)) = event {
+        let scale_factor = state.scale_factor();
+
+        if scale_factor != previous_scale_factor {
+            if let Some(message) = &window_subs.on_scale_factor_changed {
+                messages.extend(message(scale_factor));
+            }
+        }
+
+        if let Some(message) = &window_subs.on_resized {
+            messages.extend(message(state.window_info().logical_size(), scale_factor));
+        }
+
+        return;
+    }
+
+    if let baseview::Event::Keyboard(key_event) = event {
+        if let Some(message) = &window_subs.on_unmapped_key {
+            if crate::conversion::baseview_to_iced_keycode(key_event.code).is_none() {
+                messages.extend(message(key_event));
+            }
+        }
+
+        return;
+    }
+
+    let message = match event {
+        baseview::Event::Window(baseview::WindowEvent::Focused) => &window_subs.on_focused,
+        baseview::Event::Window(baseview::WindowEvent::Unfocused) => &window_subs.on_unfocused,
+        baseview::Event::Mouse(baseview::MouseEvent::CursorEntered) => {
+            &window_subs.on_cursor_entered
+        }
+        baseview::Event::Mouse(baseview::MouseEvent::CursorLeft) => &window_subs.on_cursor_left,
+        _ => return,
+    };
+
+    if let Some(message) = message {
+        messages.extend(message());
+    }
+}
+
+/// Handles the [`WindowRequest`]s the application has made through commands. Resulting events are
+/// added to `pending_events`, and the messages the application wants to receive are returned.
+fn process_window_requests<A: Application + 'static>(
+    state: &mut State<A>,
+    window_queue: &mut WindowQueue,
+    window_subs: &WindowSubs<A::Message>,
+    settings: &crate::settings::IcedBaseviewSettings,
+    ime_cursor_area: &Mutex<Option<Rectangle>>,
+    debug: &mut Debug,
+    pending_events: &mut VecDeque<RuntimeEvent<A::Message>>,
+) -> Vec<A::Message>
+where
+    <A::Renderer as core::Renderer>::Theme: StyleSheet,
+{
+    let mut messages = Vec::new();
+
+    for request in window_queue.take_requests() {
+        match request {
+            WindowRequest::Zoom(request) => {
+                let zoom = request.apply(state.zoom());
+
+                // The zoom factor only applies to the system scale factor, so other scale policies
+                // ignore it and there is no change to report
+                if !state.is_zoomable() || (zoom - state.zoom()).abs() < f64::EPSILON {
+                    continue;
+                }
+
+                // Resize the window so the logical size of its contents stays the same
+                let content_size = state.logical_size();
+                state.set_zoom(zoom);
+
+                let scale = state.viewport().scale_factor() / state.scale_factor();
+                let size = settings.size_constraints.check_size(
+                    baseview::Size::new(
+                        f64::from(content_size.width) * scale,
+                        f64::from(content_size.height) * scale,
+                    ),
+                    state.window_info().logical_size(),
+                );
+
+                pending_events.push_back(host_resize(state, window_queue, debug, size));
+
+                if let Some(message) = &window_subs.on_zoom_changed {
+                    if let Some(message) = message(zoom) {
+                        messages.push(message);
+                    }
+                }
+            }
+            WindowRequest::SetImeCursorArea(area) => {
+                // Shared in window coordinates, since that's where the candidate window goes
+                let cursor_mapping = state.cursor_mapping();
+                let area = area.map(|area| {
+                    let position = cursor_mapping.unmap(Point::new(area.x, area.y));
+
+                    Rectangle {
+                        x: position.x as f32,
+                        y: position.y as f32,
+                        width: (f64::from(area.width) / cursor_mapping.scale) as f32,
+                        height: (f64::from(area.height) / cursor_mapping.scale) as f32,
+                    }
+                });
+
+                if let Ok(mut ime_cursor_area) = ime_cursor_area.lock() {
+                    *ime_cursor_area = area;
+                }
+            }
+        }
+    }
+
+    messages
+}
+
+/// Returns `true` if the window should process every frame, instead of only the frames in which
+/// something has happened.
+fn wants_every_frame<Message>(
+    settings: &crate::settings::IcedBaseviewSettings,
+    window_subs: &WindowSubs<Message>,
+) -> bool {
+    settings.always_redraw || window_subs.on_frame.is_some() || window_subs.on_frame_info.is_some()
+}
+
+/// Wakes the instance's own waker, marking the window as dirty so the next frame gets processed.
+async fn request_frame() {
+    futures::future::poll_fn(|cx| {
+        cx.waker().wake_by_ref();
+
+        std::task::Poll::Ready(())
+    })
+    .await
+}
+
+/// Makes the current size and scale of the window available to its [`WindowHandle`].
+///
+/// [`WindowHandle`]: crate::window::WindowHandle
+fn share_window_info<A: Application>(state: &State<A>, window_info: &Mutex<baseview::WindowInfo>)
+where
+    <A::Renderer as core::Renderer>::Theme: StyleSheet,
+{
+    if let Ok(mut window_info) = window_info.lock() {
+        *window_info = state.window_info();
+    }
 }
 
 /// Builds a [`UserInterface`] for the provided [`Application`], logging
@@ -600,6 +1133,34 @@ where
     user_interface
 }
 
+/// Draws the [`UserInterface`] at its offset within the window, returning the resulting mouse
+/// interaction.
+fn draw<A: Application>(
+    user_interface: &mut UserInterface<'_, A::Message, A::Renderer>,
+    renderer: &mut A::Renderer,
+    state: &State<A>,
+) -> mouse::Interaction
+where
+    <A::Renderer as core::Renderer>::Theme: StyleSheet,
+{
+    use crate::core::Renderer as _;
+
+    let mut mouse_interaction = mouse::Interaction::default();
+
+    renderer.with_translation(state.layout_offset(), |renderer| {
+        mouse_interaction = user_interface.draw(
+            renderer,
+            state.theme(),
+            &renderer::Style {
+                text_color: state.text_color(),
+            },
+            state.cursor(),
+        );
+    });
+
+    mouse_interaction
+}
+
 /// Updates an [`Application`] by feeding it the provided messages, spawning any
 /// resulting [`Command`], and tracking its [`Subscription`].
 pub fn update<A: Application, E: Executor>(
@@ -608,6 +1169,7 @@ pub fn update<A: Application, E: Executor>(
     state: &State<A>,
     renderer: &mut A::Renderer,
     runtime: &mut Runtime<E, Proxy<A::Message>, A::Message>,
+    window_context: &WindowContext,
     clipboard: &mut Clipboard,
     debug: &mut Debug,
     messages: &mut Vec<A::Message>,
@@ -624,7 +1186,7 @@ pub fn update<A: Application, E: Executor>(
         debug.log_message(&message);
 
         debug.update_started();
-        let command = runtime.enter(|| application.update(message));
+        let command = window_context.enter(|| runtime.enter(|| application.update(message)));
 
         #[cfg(feature = "trace")]
         let _ = update_span.exit();
@@ -690,7 +1252,7 @@ pub fn run_command<A, E>(
                     application,
                     current_cache,
                     renderer,
-                    state.logical_size(),
+                    state.layout_size(),
                     debug,
                 );
 
@@ -1,10 +1,11 @@
-use baseview::WindowScalePolicy;
+use baseview::{PhySize, WindowInfo, WindowScalePolicy};
 
 use crate::application::Application;
 use crate::application::{self, StyleSheet as _};
+use crate::conversion::CursorMapping;
 use crate::core;
 use crate::core::mouse;
-use crate::core::{Color, Size};
+use crate::core::{Color, Size, Vector};
 use crate::graphics::Viewport;
 use crate::runtime::Debug;
 
@@ -25,34 +26,71 @@ where
     application: PhantomData<A>,
 
     system_scale_factor: f64,
-    scale_policy: WindowScalePolicy,
+    host_scale_factor: Option<f64>,
+    zoom: f64,
+    scale_policy: ScalePolicy,
     modifiers: iced_runtime::core::keyboard::Modifiers,
 }
 
+/// Determines the scale factor of the [`Viewport`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScalePolicy {
+    /// Use the given scale factor.
+    ScaleFactor(f64),
+    /// Use the system's scale factor.
+    SystemScaleFactor,
+    /// Lay the user interface out at the given logical size, and derive the scale factor from
+    /// the size of the window.
+    ScaleToFit(baseview::Size),
+}
+
+impl ScalePolicy {
+    fn from_application<A: Application>(application: &A) -> Self
+    where
+        <A::Renderer as core::Renderer>::Theme: application::StyleSheet,
+    {
+        match application.scale_to_fit() {
+            Some(design_size) => ScalePolicy::ScaleToFit(design_size),
+            None => match application.scale_policy() {
+                WindowScalePolicy::ScaleFactor(scale) => ScalePolicy::ScaleFactor(scale),
+                WindowScalePolicy::SystemScaleFactor => ScalePolicy::SystemScaleFactor,
+            },
+        }
+    }
+}
+
 impl<A: Application> State<A>
 where
     <A::Renderer as core::Renderer>::Theme: application::StyleSheet,
 {
-    /// Creates a new [`State`] for the provided [`Application`] and window.
-    pub fn new(application: &A, viewport: Viewport) -> Self {
+    /// Creates a new [`State`] for the provided [`Application`] and a window with the given
+    /// physical size and system scale factor.
+    pub fn new(application: &A, physical_size: Size<u32>, system_scale_factor: f64) -> Self {
         let title = application.title();
         let theme = application.theme();
-        let scale_policy = application.scale_policy();
+        let scale_policy = ScalePolicy::from_application(application);
         let appearance = theme.appearance(&application.style());
 
-        Self {
+        let mut state = Self {
             title,
-            viewport,
+            viewport: Viewport::with_physical_size(physical_size, system_scale_factor),
             viewport_version: 0,
             cursor_position: None,
             theme,
             appearance,
             application: PhantomData,
 
-            system_scale_factor: 1.0,
+            system_scale_factor,
+            host_scale_factor: None,
+            zoom: 1.0,
             scale_policy,
             modifiers: Default::default(),
-        }
+        };
+
+        state.viewport =
+            Viewport::with_physical_size(physical_size, state.viewport_scale_factor(physical_size));
+
+        state
     }
 
     /// Returns the current [`Viewport`] of the [`State`].
@@ -77,6 +115,91 @@ where
         self.viewport.logical_size()
     }
 
+    /// Returns the logical [`Size`] the user interface is laid out at. This is the logical size of
+    /// the [`Viewport`], unless the user interface is scaled to fit a fixed design size.
+    pub fn layout_size(&self) -> Size<f32> {
+        self.scaling().layout_size(&self.viewport)
+    }
+
+    /// Returns the offset of the user interface within the [`Viewport`], in logical coordinates.
+    ///
+    /// When scaling to fit a design size with a different aspect ratio than the window, the user
+    /// interface is centered and the remaining space is filled with the background color.
+    pub fn layout_offset(&self) -> Vector {
+        self.scaling().layout_offset(&self.viewport)
+    }
+
+    /// Returns how positions reported by baseview map to the logical coordinates of the user
+    /// interface.
+    ///
+    /// Baseview reports positions in logical coordinates of its own window scale, which differs
+    /// from the scale of the [`Viewport`] whenever the application uses a custom scale factor, a
+    /// zoom factor, or scales its user interface to fit.
+    pub fn cursor_mapping(&self) -> CursorMapping {
+        self.scaling().cursor_mapping(&self.viewport)
+    }
+
+    /// Returns the scale factor of the window. This is the system scale factor, unless the host has
+    /// overridden it with [`set_host_scale_factor`](Self::set_host_scale_factor).
+    pub fn scale_factor(&self) -> f64 {
+        self.host_scale_factor.unwrap_or(self.system_scale_factor)
+    }
+
+    /// Overrides the system scale factor with the one provided by the host.
+    pub fn set_host_scale_factor(&mut self, scale_factor: f64) {
+        self.host_scale_factor = Some(scale_factor);
+    }
+
+    /// Returns the zoom factor the user has chosen on top of the system scale factor.
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    /// Returns `true` if the zoom factor affects the [`Viewport`], which is only the case when
+    /// the application uses the system scale factor.
+    pub fn is_zoomable(&self) -> bool {
+        self.scale_policy == ScalePolicy::SystemScaleFactor
+    }
+
+    /// Sets the zoom factor, updating the [`Viewport`] accordingly.
+    pub fn set_zoom(&mut self, zoom: f64) {
+        self.zoom = zoom;
+
+        let physical_size = self.viewport.physical_size();
+
+        self.viewport =
+            Viewport::with_physical_size(physical_size, self.viewport_scale_factor(physical_size));
+
+        self.viewport_version = self.viewport_version.wrapping_add(1);
+    }
+
+    /// Returns the size and scale of the window, as seen by the host.
+    pub fn window_info(&self) -> WindowInfo {
+        let physical_size = self.viewport.physical_size();
+
+        WindowInfo::from_physical_size(
+            PhySize {
+                width: physical_size.width,
+                height: physical_size.height,
+            },
+            self.scale_factor(),
+        )
+    }
+
+    /// Returns the [`WindowInfo`] baseview will report once the window has been resized to the
+    /// given logical size.
+    pub fn resized_window_info(&self, size: baseview::Size) -> WindowInfo {
+        let scale_factor = self.scale_factor();
+
+        WindowInfo::from_physical_size(
+            PhySize {
+                width: (size.width * scale_factor).round() as u32,
+                height: (size.height * scale_factor).round() as u32,
+            },
+            self.system_scale_factor,
+        )
+    }
+
     /// Returns the current cursor position of the [`State`].
     pub fn cursor(&self) -> mouse::Cursor {
         self.cursor_position
@@ -107,17 +230,14 @@ where
                 // Cache system window info in case users changes their scale policy in the future.
                 self.system_scale_factor = window_info.scale();
 
-                let scale = match self.scale_policy {
-                    WindowScalePolicy::ScaleFactor(scale) => scale,
-                    WindowScalePolicy::SystemScaleFactor => self.system_scale_factor,
-                };
+                let physical_size = Size::new(
+                    window_info.physical_size().width,
+                    window_info.physical_size().height,
+                );
 
                 self.viewport = Viewport::with_physical_size(
-                    Size::new(
-                        window_info.physical_size().width,
-                        window_info.physical_size().height,
-                    ),
-                    scale,
+                    physical_size,
+                    self.viewport_scale_factor(physical_size),
                 );
 
                 self.viewport_version = self.viewport_version.wrapping_add(1);
@@ -126,10 +246,7 @@ where
                 position,
                 modifiers: _,
             }) => {
-                self.cursor_position = Some(crate::core::Point {
-                    x: position.x as f32,
-                    y: position.y as f32,
-                });
+                self.cursor_position = Some(self.cursor_mapping().map(*position));
 
                 // TODO: Encode cursor moving outside of the window.
             }
@@ -165,39 +282,19 @@ where
         }
 
         // Update scale policy
-        let new_scale_policy = application.scale_policy();
-
-        match &new_scale_policy {
-            WindowScalePolicy::SystemScaleFactor => match &self.scale_policy {
-                WindowScalePolicy::SystemScaleFactor => {}
-                WindowScalePolicy::ScaleFactor(_) => {
-                    self.scale_policy = WindowScalePolicy::SystemScaleFactor;
+        let new_scale_policy = ScalePolicy::from_application(application);
 
-                    self.viewport = Viewport::with_physical_size(
-                        self.viewport.physical_size(),
-                        self.system_scale_factor,
-                    );
+        if new_scale_policy != self.scale_policy {
+            self.scale_policy = new_scale_policy;
 
-                    self.viewport_version = self.viewport_version.wrapping_add(1);
-                }
-            },
-            WindowScalePolicy::ScaleFactor(new_scale) => {
-                let matches = match &self.scale_policy {
-                    WindowScalePolicy::SystemScaleFactor => false,
-                    WindowScalePolicy::ScaleFactor(scale) => {
-                        (*scale - *new_scale).abs() < f64::EPSILON
-                    }
-                };
-
-                if !matches {
-                    self.scale_policy = WindowScalePolicy::ScaleFactor(*new_scale);
+            let physical_size = self.viewport.physical_size();
 
-                    self.viewport =
-                        Viewport::with_physical_size(self.viewport.physical_size(), *new_scale);
+            self.viewport = Viewport::with_physical_size(
+                physical_size,
+                self.viewport_scale_factor(physical_size),
+            );
 
-                    self.viewport_version = self.viewport_version.wrapping_add(1);
-                }
-            }
+            self.viewport_version = self.viewport_version.wrapping_add(1);
         }
 
         // Update theme and appearance
@@ -205,7 +302,172 @@ where
         self.appearance = self.theme.appearance(&application.style());
     }
 
+    /// Returns the scale factor the [`Viewport`] should use for the given physical size, according
+    /// to the current scale policy.
+    fn viewport_scale_factor(&self, physical_size: Size<u32>) -> f64 {
+        self.scaling().viewport_scale_factor(physical_size)
+    }
+
+    fn scaling(&self) -> Scaling {
+        Scaling {
+            scale_policy: self.scale_policy,
+            system_scale_factor: self.system_scale_factor,
+            scale_factor: self.scale_factor(),
+            zoom: self.zoom,
+        }
+    }
+
     pub(crate) fn modifiers_mut(&mut self) -> &mut iced_runtime::core::keyboard::Modifiers {
         &mut self.modifiers
     }
 }
+
+/// Everything that decides how the user interface is scaled within the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Scaling {
+    scale_policy: ScalePolicy,
+    /// The scale factor baseview reports positions in.
+    system_scale_factor: f64,
+    /// The scale factor of the window, which the host may have overridden.
+    scale_factor: f64,
+    zoom: f64,
+}
+
+impl Scaling {
+    fn viewport_scale_factor(&self, physical_size: Size<u32>) -> f64 {
+        match self.scale_policy {
+            ScalePolicy::ScaleFactor(scale) => scale,
+            ScalePolicy::SystemScaleFactor => self.scale_factor * self.zoom,
+            ScalePolicy::ScaleToFit(design_size) => {
+                let scale = (f64::from(physical_size.width) / design_size.width)
+                    .min(f64::from(physical_size.height) / design_size.height);
+
+                if scale.is_finite() && scale > 0.0 {
+                    scale
+                } else {
+                    self.scale_factor
+                }
+            }
+        }
+    }
+
+    fn layout_size(&self, viewport: &Viewport) -> Size<f32> {
+        match self.scale_policy {
+            ScalePolicy::ScaleToFit(design_size) => {
+                Size::new(design_size.width as f32, design_size.height as f32)
+            }
+            _ => viewport.logical_size(),
+        }
+    }
+
+    fn layout_offset(&self, viewport: &Viewport) -> Vector {
+        let logical_size = viewport.logical_size();
+        let layout_size = self.layout_size(viewport);
+
+        Vector::new(
+            ((logical_size.width - layout_size.width) / 2.0).max(0.0),
+            ((logical_size.height - layout_size.height) / 2.0).max(0.0),
+        )
+    }
+
+    fn cursor_mapping(&self, viewport: &Viewport) -> CursorMapping {
+        CursorMapping {
+            scale: self.system_scale_factor / viewport.scale_factor(),
+            offset: self.layout_offset(viewport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSTEM_SCALE_FACTOR: f64 = 1.5;
+    const HOST_SCALE_FACTOR: f64 = 2.0;
+    const ZOOM: f64 = 1.25;
+
+    /// Checks the cursor mapping of a window that is 1200x900 physical pixels large, for a scale
+    /// policy with and without zoom and a host scale factor.
+    ///
+    /// `expected` returns the scale factor of the viewport and the offset of the user interface
+    /// for the given zoom factor and host scale factor.
+    fn check_policy(
+        scale_policy: ScalePolicy,
+        expected: impl Fn(f64, Option<f64>) -> (f64, Vector),
+    ) {
+        let physical_size = Size::new(1200, 900);
+
+        for zoom in [1.0, ZOOM] {
+            for host_scale_factor in [None, Some(HOST_SCALE_FACTOR)] {
+                let scaling = Scaling {
+                    scale_policy,
+                    system_scale_factor: SYSTEM_SCALE_FACTOR,
+                    scale_factor: host_scale_factor.unwrap_or(SYSTEM_SCALE_FACTOR),
+                    zoom,
+                };
+                let viewport = Viewport::with_physical_size(
+                    physical_size,
+                    scaling.viewport_scale_factor(physical_size),
+                );
+                let mapping = scaling.cursor_mapping(&viewport);
+                let (scale, offset) = expected(zoom, host_scale_factor);
+
+                let case = format!(
+                    "{:?} with zoom {} and host scale factor {:?}",
+                    scale_policy, zoom, host_scale_factor
+                );
+                assert!((viewport.scale_factor() - scale).abs() < 1e-9, "{}", case);
+
+                // Baseview reports this position in its own logical coordinates, which are based
+                // on the system scale factor
+                let physical_position = (600.0, 300.0);
+                let position = baseview::Point::new(
+                    physical_position.0 / SYSTEM_SCALE_FACTOR,
+                    physical_position.1 / SYSTEM_SCALE_FACTOR,
+                );
+
+                let mapped = mapping.map(position);
+                let expected_x = (physical_position.0 / scale) as f32 - offset.x;
+                let expected_y = (physical_position.1 / scale) as f32 - offset.y;
+                assert!((mapped.x - expected_x).abs() < 1e-3, "{}", case);
+                assert!((mapped.y - expected_y).abs() < 1e-3, "{}", case);
+
+                let unmapped = mapping.unmap(mapped);
+                assert!((unmapped.x - position.x).abs() < 1e-3, "{}", case);
+                assert!((unmapped.y - position.y).abs() < 1e-3, "{}", case);
+
+                let distance = mapping.scale_distance(30.0);
+                let expected_distance = (30.0 * SYSTEM_SCALE_FACTOR / scale) as f32;
+                assert!((distance - expected_distance).abs() < 1e-3, "{}", case);
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_mapping_with_scale_factor() {
+        // A fixed scale factor ignores both the zoom factor and the host
+        check_policy(ScalePolicy::ScaleFactor(1.25), |_, _| {
+            (1.25, Vector::new(0.0, 0.0))
+        });
+    }
+
+    #[test]
+    fn cursor_mapping_with_system_scale_factor() {
+        check_policy(ScalePolicy::SystemScaleFactor, |zoom, host_scale_factor| {
+            (
+                host_scale_factor.unwrap_or(SYSTEM_SCALE_FACTOR) * zoom,
+                Vector::new(0.0, 0.0),
+            )
+        });
+    }
+
+    #[test]
+    fn cursor_mapping_with_scale_to_fit() {
+        // 1200x900 physical pixels fit a 400x200 design three times, leaving 100 logical pixels
+        // of vertical space that are split evenly above and below the user interface
+        check_policy(
+            ScalePolicy::ScaleToFit(baseview::Size::new(400.0, 200.0)),
+            |_, _| (3.0, Vector::new(0.0, 50.0)),
+        );
+    }
+}
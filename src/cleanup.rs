@@ -0,0 +1,139 @@
+//! Run the cleanup futures of closed windows without blocking the GUI thread.
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+use crate::futures::futures::future::BoxFuture;
+
+/// The worker thread that runs cleanup futures, if it is running.
+///
+/// A single worker is shared by all windows in the process. It exits once it has no futures left
+/// to run, so it does not outlive a plugin that is being unloaded, and is started again for the
+/// next window that closes.
+static WORKER: Mutex<Option<Worker>> = Mutex::new(None);
+
+struct Worker {
+    sender: Sender<Cleanup>,
+    thread: Thread,
+}
+
+struct Cleanup {
+    future: BoxFuture<'static, ()>,
+    /// `None` if the timeout is too long to be represented.
+    deadline: Option<Instant>,
+}
+
+/// Runs `future` on the cleanup worker thread, and drops it if it has not completed after
+/// `timeout`.
+pub(crate) fn spawn(future: BoxFuture<'static, ()>, timeout: Duration) {
+    let mut cleanup = Cleanup {
+        future,
+        deadline: Instant::now().checked_add(timeout),
+    };
+
+    let mut worker = WORKER.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(running) = &*worker {
+        match running.sender.send(cleanup) {
+            Ok(()) => {
+                running.thread.unpark();
+
+                return;
+            }
+            // The worker has stopped unexpectedly, so a new one takes over
+            Err(mpsc::SendError(unsent)) => cleanup = unsent,
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let _ = sender.send(cleanup);
+
+    match thread::Builder::new()
+        .name(String::from("iced_baseview cleanup"))
+        .spawn(move || run(receiver))
+    {
+        Ok(handle) => {
+            *worker = Some(Worker {
+                sender,
+                thread: handle.thread().clone(),
+            });
+        }
+        Err(error) => {
+            log::error!("could not start the cleanup thread: {}", error);
+        }
+    }
+}
+
+/// Polls the cleanup futures until all of them have completed or timed out.
+fn run(receiver: Receiver<Cleanup>) {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut cleanups: Vec<Cleanup> = Vec::new();
+
+    loop {
+        cleanups.extend(receiver.try_iter());
+
+        let now = Instant::now();
+        cleanups.retain_mut(|cleanup| {
+            if cleanup.deadline.map_or(false, |deadline| deadline <= now) {
+                log::warn!("a cleanup future did not complete in time and has been dropped");
+
+                return false;
+            }
+
+            let poll = panic::catch_unwind(AssertUnwindSafe(|| {
+                std::future::Future::poll(cleanup.future.as_mut(), &mut context)
+            }));
+
+            match poll {
+                Ok(poll) => poll.is_pending(),
+                Err(payload) => {
+                    let message = crate::crash::panic_message(payload.as_ref());
+                    log::error!("a cleanup future panicked: {}", message);
+
+                    false
+                }
+            }
+        });
+
+        if cleanups.is_empty() {
+            // Futures are only sent while holding the lock, so none can get lost once the worker
+            // has been removed.
+            let mut worker = WORKER.lock().unwrap_or_else(PoisonError::into_inner);
+
+            match receiver.try_recv() {
+                Ok(cleanup) => cleanups.push(cleanup),
+                Err(_) => {
+                    *worker = None;
+
+                    return;
+                }
+            }
+
+            continue;
+        }
+
+        match cleanups.iter().filter_map(|cleanup| cleanup.deadline).min() {
+            Some(deadline) => {
+                thread::park_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => thread::park(),
+        }
+    }
+}
+
+/// Wakes the worker thread when one of its futures can make progress.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
@@ -1,18 +1,66 @@
 use baseview::Event as BaseEvent;
+use iced_runtime::core::keyboard::KeyCode as ICode;
 use iced_runtime::core::mouse::Button as IcedMouseButton;
 use iced_runtime::core::mouse::Event as IcedMouseEvent;
 use iced_runtime::core::window::Event as IcedWindowEvent;
 use iced_runtime::core::Event as IcedEvent;
 use iced_runtime::core::Point;
+use iced_runtime::core::Vector;
 use iced_runtime::keyboard::Event as IcedKeyEvent;
 use iced_runtime::keyboard::Modifiers as IcedModifiers;
+use keyboard_types::Code as KCode;
 use keyboard_types::Modifiers as BaseviewModifiers;
 
+/// Describes how positions reported by baseview map to the logical coordinates of the user
+/// interface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CursorMapping {
+    /// The factor baseview's logical coordinates are multiplied by.
+    pub scale: f64,
+    /// The offset of the user interface within the window, in iced's logical coordinates.
+    pub offset: Vector,
+}
+
+impl CursorMapping {
+    /// Maps a position reported by baseview to the logical coordinates of the user interface.
+    pub fn map(&self, position: baseview::Point) -> Point {
+        Point::new(
+            (position.x * self.scale) as f32 - self.offset.x,
+            (position.y * self.scale) as f32 - self.offset.y,
+        )
+    }
+
+    /// Maps a position in the logical coordinates of the user interface back to the logical
+    /// coordinates of the window, as used by baseview.
+    pub fn unmap(&self, position: Point) -> baseview::Point {
+        baseview::Point::new(
+            f64::from(position.x + self.offset.x) / self.scale,
+            f64::from(position.y + self.offset.y) / self.scale,
+        )
+    }
+
+    /// Scales a distance reported by baseview, such as a pixel scroll delta, to the logical
+    /// coordinates of the user interface.
+    pub fn scale_distance(&self, distance: f32) -> f32 {
+        (f64::from(distance) * self.scale) as f32
+    }
+}
+
+impl Default for CursorMapping {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            offset: Vector::new(0.0, 0.0),
+        }
+    }
+}
+
 pub fn baseview_to_iced_events(
     event: BaseEvent,
     iced_events: &mut Vec<IcedEvent>,
     iced_modifiers: &mut IcedModifiers,
     ignore_non_modifier_keys: bool,
+    cursor_mapping: CursorMapping,
 ) {
     match event {
         BaseEvent::Mouse(mouse_event) => match mouse_event {
@@ -24,7 +72,7 @@ pub fn baseview_to_iced_events(
                     iced_events.push(event);
                 }
                 iced_events.push(IcedEvent::Mouse(IcedMouseEvent::CursorMoved {
-                    position: Point::new(position.x as f32, position.y as f32),
+                    position: cursor_mapping.map(position),
                 }));
             }
             baseview::MouseEvent::ButtonPressed { button, modifiers } => {
@@ -57,7 +105,10 @@ pub fn baseview_to_iced_events(
                         iced_events.push(event);
                     }
                     iced_events.push(IcedEvent::Mouse(IcedMouseEvent::WheelScrolled {
-                        delta: iced_runtime::core::mouse::ScrollDelta::Pixels { x, y },
+                        delta: iced_runtime::core::mouse::ScrollDelta::Pixels {
+                            x: cursor_mapping.scale_distance(x),
+                            y: cursor_mapping.scale_distance(y),
+                        },
                     }));
                 }
             },
@@ -163,16 +214,6 @@ fn baseview_mouse_button_to_iced(id: baseview::MouseButton) -> IcedMouseButton {
     }
 }
 
-/*
-/// Converts a physical cursor position to a logical `Point`.
-pub fn cursor_position(position: PhyPoint, scale_factor: f64) -> Point {
-    Point::new(
-        (f64::from(position.x) * scale_factor) as f32,
-        (f64::from(position.y) * scale_factor) as f32,
-    )
-}
-*/
-
 /*
 // As defined in: http://www.unicode.org/faq/private_use.html
 fn is_private_use_character(c: char) -> bool {
@@ -185,151 +226,238 @@ fn is_private_use_character(c: char) -> bool {
 }
 */
 
-fn baseview_to_iced_keycode(
-    code: keyboard_types::Code,
-) -> Option<iced_runtime::core::keyboard::KeyCode> {
-    use iced_runtime::core::keyboard::KeyCode as ICode;
-    use keyboard_types::Code as KCode;
-
-    match code {
-        KCode::Digit1 => Some(ICode::Key1),
-        KCode::Digit2 => Some(ICode::Key2),
-        KCode::Digit3 => Some(ICode::Key3),
-        KCode::Digit4 => Some(ICode::Key4),
-        KCode::Digit5 => Some(ICode::Key5),
-        KCode::Digit6 => Some(ICode::Key6),
-        KCode::Digit7 => Some(ICode::Key7),
-        KCode::Digit8 => Some(ICode::Key8),
-        KCode::Digit9 => Some(ICode::Key9),
-        KCode::Digit0 => Some(ICode::Key0),
-
-        KCode::KeyA => Some(ICode::A),
-        KCode::KeyB => Some(ICode::B),
-        KCode::KeyC => Some(ICode::C),
-        KCode::KeyD => Some(ICode::D),
-        KCode::KeyE => Some(ICode::E),
-        KCode::KeyF => Some(ICode::F),
-        KCode::KeyG => Some(ICode::G),
-        KCode::KeyH => Some(ICode::H),
-        KCode::KeyI => Some(ICode::I),
-        KCode::KeyJ => Some(ICode::J),
-        KCode::KeyK => Some(ICode::K),
-        KCode::KeyL => Some(ICode::L),
-        KCode::KeyM => Some(ICode::M),
-        KCode::KeyN => Some(ICode::N),
-        KCode::KeyO => Some(ICode::O),
-        KCode::KeyP => Some(ICode::P),
-        KCode::KeyQ => Some(ICode::Q),
-        KCode::KeyR => Some(ICode::R),
-        KCode::KeyS => Some(ICode::S),
-        KCode::KeyT => Some(ICode::T),
-        KCode::KeyU => Some(ICode::U),
-        KCode::KeyV => Some(ICode::V),
-        KCode::KeyW => Some(ICode::W),
-        KCode::KeyX => Some(ICode::X),
-        KCode::KeyY => Some(ICode::Y),
-        KCode::KeyZ => Some(ICode::Z),
-
-        KCode::Escape => Some(ICode::Escape),
-
-        KCode::F1 => Some(ICode::F1),
-        KCode::F2 => Some(ICode::F2),
-        KCode::F3 => Some(ICode::F3),
-        KCode::F4 => Some(ICode::F4),
-        KCode::F5 => Some(ICode::F5),
-        KCode::F6 => Some(ICode::F6),
-        KCode::F7 => Some(ICode::F7),
-        KCode::F8 => Some(ICode::F8),
-        KCode::F9 => Some(ICode::F9),
-        KCode::F10 => Some(ICode::F10),
-        KCode::F11 => Some(ICode::F11),
-        KCode::F12 => Some(ICode::F12),
-
-        KCode::PrintScreen => Some(ICode::Snapshot),
-        KCode::ScrollLock => Some(ICode::Scroll),
-        KCode::Pause => Some(ICode::Pause),
+/// Defines [`baseview_to_iced_keycode`] as a `match` on the given mappings, and lists them in
+/// `KEY_CODES` for the tests.
+macro_rules! key_codes {
+    ($($code:ident => $key_code:ident,)*) => {
+        /// Maps a physical key code reported by baseview to iced's key code, if iced can represent
+        /// it. Codes that are missing here have no equivalent in iced, see
+        /// [`WindowSubs::on_unmapped_key`] for receiving them anyway.
+        ///
+        /// [`WindowSubs::on_unmapped_key`]: crate::window::WindowSubs::on_unmapped_key
+        pub(crate) fn baseview_to_iced_keycode(code: KCode) -> Option<ICode> {
+            match code {
+                $(KCode::$code => Some(ICode::$key_code),)*
+                _ => None,
+            }
+        }
 
-        KCode::Insert => Some(ICode::Insert),
-        KCode::Home => Some(ICode::Home),
-        KCode::Delete => Some(ICode::Delete),
-        KCode::End => Some(ICode::End),
-        KCode::PageDown => Some(ICode::PageDown),
-        KCode::PageUp => Some(ICode::PageUp),
+        #[cfg(test)]
+        const KEY_CODES: &[(KCode, ICode)] = &[$((KCode::$code, ICode::$key_code),)*];
+    };
+}
 
-        KCode::ArrowLeft => Some(ICode::Left),
-        KCode::ArrowUp => Some(ICode::Up),
-        KCode::ArrowRight => Some(ICode::Right),
-        KCode::ArrowDown => Some(ICode::Down),
+key_codes! {
+    Digit1 => Key1,
+    Digit2 => Key2,
+    Digit3 => Key3,
+    Digit4 => Key4,
+    Digit5 => Key5,
+    Digit6 => Key6,
+    Digit7 => Key7,
+    Digit8 => Key8,
+    Digit9 => Key9,
+    Digit0 => Key0,
+    KeyA => A,
+    KeyB => B,
+    KeyC => C,
+    KeyD => D,
+    KeyE => E,
+    KeyF => F,
+    KeyG => G,
+    KeyH => H,
+    KeyI => I,
+    KeyJ => J,
+    KeyK => K,
+    KeyL => L,
+    KeyM => M,
+    KeyN => N,
+    KeyO => O,
+    KeyP => P,
+    KeyQ => Q,
+    KeyR => R,
+    KeyS => S,
+    KeyT => T,
+    KeyU => U,
+    KeyV => V,
+    KeyW => W,
+    KeyX => X,
+    KeyY => Y,
+    KeyZ => Z,
+    Escape => Escape,
+    F1 => F1,
+    F2 => F2,
+    F3 => F3,
+    F4 => F4,
+    F5 => F5,
+    F6 => F6,
+    F7 => F7,
+    F8 => F8,
+    F9 => F9,
+    F10 => F10,
+    F11 => F11,
+    F12 => F12,
+    F13 => F13,
+    F14 => F14,
+    F15 => F15,
+    F16 => F16,
+    F17 => F17,
+    F18 => F18,
+    F19 => F19,
+    F20 => F20,
+    F21 => F21,
+    F22 => F22,
+    F23 => F23,
+    F24 => F24,
+    PrintScreen => Snapshot,
+    ScrollLock => Scroll,
+    Pause => Pause,
+    Insert => Insert,
+    Home => Home,
+    Delete => Delete,
+    End => End,
+    PageDown => PageDown,
+    PageUp => PageUp,
+    ArrowLeft => Left,
+    ArrowUp => Up,
+    ArrowRight => Right,
+    ArrowDown => Down,
+    Backspace => Backspace,
+    Enter => Enter,
+    Space => Space,
+    Tab => Tab,
+    NumLock => Numlock,
+    Numpad0 => Numpad0,
+    Numpad1 => Numpad1,
+    Numpad2 => Numpad2,
+    Numpad3 => Numpad3,
+    Numpad4 => Numpad4,
+    Numpad5 => Numpad5,
+    Numpad6 => Numpad6,
+    Numpad7 => Numpad7,
+    Numpad8 => Numpad8,
+    Numpad9 => Numpad9,
+    NumpadAdd => NumpadAdd,
+    NumpadDivide => NumpadDivide,
+    NumpadDecimal => NumpadDecimal,
+    NumpadComma => NumpadComma,
+    NumpadEnter => NumpadEnter,
+    NumpadEqual => NumpadEquals,
+    NumpadMultiply => NumpadMultiply,
+    NumpadSubtract => NumpadSubtract,
+    NumpadStar => Asterisk,
+    Convert => Convert,
+    NonConvert => NoConvert,
+    KanaMode => Kana,
+    IntlYen => Yen,
+    IntlRo => AbntC1,
+    IntlBackslash => OEM102,
+    AltLeft => LAlt,
+    AltRight => RAlt,
+    ControlLeft => LControl,
+    ControlRight => RControl,
+    ShiftLeft => LShift,
+    ShiftRight => RShift,
+    MetaLeft => LWin,
+    MetaRight => RWin,
+    CapsLock => Capital,
+    ContextMenu => Apps,
+    Backquote => Grave,
+    Minus => Minus,
+    Equal => Equals,
+    BracketLeft => LBracket,
+    BracketRight => RBracket,
+    Backslash => Backslash,
+    Semicolon => Semicolon,
+    Quote => Apostrophe,
+    Comma => Comma,
+    Period => Period,
+    Slash => Slash,
+    Copy => Copy,
+    Paste => Paste,
+    Cut => Cut,
+    MediaSelect => MediaSelect,
+    MediaStop => MediaStop,
+    MediaPlayPause => PlayPause,
+    MediaTrackNext => NextTrack,
+    MediaTrackPrevious => PrevTrack,
+    AudioVolumeMute => Mute,
+    AudioVolumeDown => VolumeDown,
+    AudioVolumeUp => VolumeUp,
+    BrowserBack => WebBack,
+    BrowserForward => WebForward,
+    BrowserFavorites => WebFavorites,
+    BrowserHome => WebHome,
+    BrowserRefresh => WebRefresh,
+    BrowserSearch => WebSearch,
+    BrowserStop => WebStop,
+    LaunchMail => Mail,
+    LaunchApp1 => MyComputer,
+    LaunchApp2 => Calculator,
+    Power => Power,
+    Sleep => Sleep,
+    WakeUp => Wake,
+}
 
-        KCode::Backspace => Some(ICode::Backspace),
-        KCode::Enter => Some(ICode::Enter),
-        KCode::Space => Some(ICode::Space),
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        KCode::NumLock => Some(ICode::Numlock),
-        KCode::Numpad0 => Some(ICode::Numpad0),
-        KCode::Numpad1 => Some(ICode::Numpad1),
-        KCode::Numpad2 => Some(ICode::Numpad2),
-        KCode::Numpad3 => Some(ICode::Numpad3),
-        KCode::Numpad4 => Some(ICode::Numpad4),
-        KCode::Numpad5 => Some(ICode::Numpad5),
-        KCode::Numpad6 => Some(ICode::Numpad6),
-        KCode::Numpad7 => Some(ICode::Numpad7),
-        KCode::Numpad8 => Some(ICode::Numpad8),
-        KCode::Numpad9 => Some(ICode::Numpad9),
-        KCode::NumpadAdd => Some(ICode::NumpadAdd),
-        KCode::NumpadDivide => Some(ICode::NumpadDivide),
-        KCode::NumpadDecimal => Some(ICode::NumpadDecimal),
-        KCode::NumpadComma => Some(ICode::NumpadComma),
-        KCode::NumpadEnter => Some(ICode::NumpadEnter),
-        KCode::NumpadEqual => Some(ICode::NumpadEquals),
-        KCode::NumpadMultiply => Some(ICode::NumpadMultiply),
-        KCode::NumpadSubtract => Some(ICode::NumpadSubtract),
+    #[test]
+    fn key_codes_are_mapped_once() {
+        for (index, (code, _)) in KEY_CODES.iter().enumerate() {
+            assert!(
+                !KEY_CODES[..index].iter().any(|(other, _)| other == code),
+                "{:?} is mapped more than once",
+                code
+            );
+        }
+    }
 
-        //KCode::AbntC1 => Some(ICode::AbntC1),    // TODO ?
-        //KCode::AbntC1 => Some(ICode::AbntC1),    // TODO ?
-        KCode::Convert => Some(ICode::Convert),
-        KCode::KanaMode => Some(ICode::Kana),
-        //KCode::Kanji => ICode::Kanji),    // TODO ?
-        KCode::NonConvert => Some(ICode::NoConvert),
-        KCode::IntlYen => Some(ICode::Yen),
+    #[test]
+    fn key_codes_keep_the_previous_mappings() {
+        let previous = [
+            (KCode::Digit1, ICode::Key1),
+            (KCode::KeyA, ICode::A),
+            (KCode::F12, ICode::F12),
+            (KCode::PrintScreen, ICode::Snapshot),
+            (KCode::ArrowLeft, ICode::Left),
+            (KCode::NumpadEqual, ICode::NumpadEquals),
+            (KCode::KanaMode, ICode::Kana),
+            (KCode::MetaLeft, ICode::LWin),
+            (KCode::Quote, ICode::Apostrophe),
+            (KCode::MediaPlayPause, ICode::PlayPause),
+        ];
 
-        KCode::AltLeft => Some(ICode::LAlt),
-        KCode::AltRight => Some(ICode::RAlt),
-        KCode::BracketLeft => Some(ICode::LBracket),
-        KCode::BracketRight => Some(ICode::RBracket),
-        KCode::ControlLeft => Some(ICode::LControl),
-        KCode::ControlRight => Some(ICode::RControl),
-        KCode::ShiftLeft => Some(ICode::LShift),
-        KCode::ShiftRight => Some(ICode::RShift),
-        KCode::MetaLeft => Some(ICode::LWin),
-        KCode::MetaRight => Some(ICode::RWin),
+        for (code, key_code) in previous {
+            assert_eq!(baseview_to_iced_keycode(code), Some(key_code));
+        }
+    }
 
-        KCode::Minus => Some(ICode::Minus),
-        KCode::Period => Some(ICode::Period),
-        //KCode::Plus => Some(ICode::Plus),    // TODO ?
-        KCode::Equal => Some(ICode::Equals),
-        KCode::Quote => Some(ICode::Apostrophe),
-        KCode::Comma => Some(ICode::Comma),
-        //KCode::Grave => Some(ICode::Grave),    // TODO ?
-        //KCode::Colon => Some(ICode::Colon),    // TODO ?
-        KCode::Semicolon => Some(ICode::Semicolon),
-        KCode::Backslash => Some(ICode::Backslash),
-        KCode::Slash => Some(ICode::Slash),
-        KCode::Tab => Some(ICode::Tab),
-        //KCode::Underline => Some(ICode::Underline),    // TODO ?
-        KCode::Copy => Some(ICode::Copy),
-        KCode::Paste => Some(ICode::Paste),
-        KCode::Cut => Some(ICode::Cut),
+    #[test]
+    fn key_codes_map_the_new_mappings() {
+        let new = [
+            (KCode::F13, ICode::F13),
+            (KCode::F24, ICode::F24),
+            (KCode::Backquote, ICode::Grave),
+            (KCode::CapsLock, ICode::Capital),
+            (KCode::ContextMenu, ICode::Apps),
+            (KCode::NumpadStar, ICode::Asterisk),
+            (KCode::IntlBackslash, ICode::OEM102),
+            (KCode::IntlRo, ICode::AbntC1),
+            (KCode::BrowserBack, ICode::WebBack),
+            (KCode::WakeUp, ICode::Wake),
+        ];
 
-        KCode::MediaSelect => Some(ICode::MediaSelect),
-        KCode::MediaStop => Some(ICode::MediaStop),
-        KCode::MediaPlayPause => Some(ICode::PlayPause),
-        KCode::AudioVolumeMute => Some(ICode::Mute),
-        KCode::AudioVolumeDown => Some(ICode::VolumeDown),
-        KCode::AudioVolumeUp => Some(ICode::VolumeUp),
-        KCode::MediaTrackNext => Some(ICode::NextTrack),
-        KCode::MediaTrackPrevious => Some(ICode::PrevTrack),
+        for (code, key_code) in new {
+            assert_eq!(baseview_to_iced_keycode(code), Some(key_code));
+        }
+    }
 
-        _ => None,
+    #[test]
+    fn unknown_key_codes_are_not_mapped() {
+        assert_eq!(baseview_to_iced_keycode(KCode::Lang1), None);
+        assert_eq!(baseview_to_iced_keycode(KCode::Lang2), None);
+        assert_eq!(baseview_to_iced_keycode(KCode::Unidentified), None);
     }
 }
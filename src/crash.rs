@@ -0,0 +1,135 @@
+//! Recover from panics in application code.
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::core::alignment;
+use crate::core::layout;
+use crate::core::mouse;
+use crate::core::renderer;
+use crate::core::text::{self, LineHeight, Shaping};
+use crate::core::widget::Tree;
+use crate::core::{Element, Layout, Length, Rectangle, Size, Widget};
+
+/// Keeps track of whether an [`Application`] has panicked.
+///
+/// [`Application`]: crate::Application
+#[derive(Debug, Default)]
+pub(crate) struct Crash {
+    message: RefCell<Option<String>>,
+    reported: Cell<bool>,
+}
+
+impl Crash {
+    /// Runs `f` and returns its result, unless it panics or a panic has been caught before.
+    pub(crate) fn catch<T>(&self, f: impl FnOnce() -> T) -> Option<T> {
+        if self.message.borrow().is_some() {
+            return None;
+        }
+
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                *self.message.borrow_mut() = Some(panic_message(payload.as_ref()));
+
+                None
+            }
+        }
+    }
+
+    /// Returns the message of the panic that has been caught, if any.
+    pub(crate) fn message(&self) -> Option<String> {
+        self.message.borrow().clone()
+    }
+
+    /// Returns the message of the panic that has been caught, if it has not been returned before.
+    pub(crate) fn take_unreported(&self) -> Option<String> {
+        if self.reported.get() {
+            return None;
+        }
+
+        let message = self.message();
+        self.reported.set(message.is_some());
+
+        message
+    }
+}
+
+/// Extracts the message from the payload of a panic.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic payload")
+    }
+}
+
+/// Returns the default view that is shown once an [`Application`] has panicked.
+///
+/// [`Application`]: crate::Application
+pub(crate) fn crashed_view<'a, Message, Renderer>(message: &str) -> Element<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    Element::new(CrashedView {
+        content: format!("The editor has crashed.\n\n{message}"),
+    })
+}
+
+/// Shows a message in the center of the window, using nothing but the text color of the theme.
+struct CrashedView {
+    content: String,
+}
+
+impl<Message, Renderer> Widget<Message, Renderer> for CrashedView
+where
+    Renderer: text::Renderer,
+{
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Fill
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        layout::Node::new(
+            limits
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .resolve(Size::ZERO),
+        )
+    }
+
+    fn draw(
+        &self,
+        _state: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        renderer.fill_text(text::Text {
+            content: &self.content,
+            bounds: Rectangle {
+                x: bounds.center_x(),
+                y: bounds.center_y(),
+                ..bounds
+            },
+            size: renderer.default_size(),
+            line_height: LineHeight::default(),
+            color: style.text_color,
+            font: renderer.default_font(),
+            horizontal_alignment: alignment::Horizontal::Center,
+            vertical_alignment: alignment::Vertical::Center,
+            shaping: Shaping::Advanced,
+        });
+    }
+}
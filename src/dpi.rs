@@ -0,0 +1,93 @@
+//! Determine the system scale factor before the window reports it.
+
+/// Returns the scale factor baseview has created `window` with when using
+/// `WindowScalePolicy::SystemScaleFactor`, if it can be determined.
+///
+/// * `logical_size` - The logical size the window was opened with.
+pub(crate) fn system_scale_factor(
+    window: &baseview::Window<'_>,
+    logical_size: baseview::Size,
+) -> Option<f64> {
+    #[cfg(target_os = "linux")]
+    {
+        x11::system_scale_factor(window, logical_size)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (window, logical_size);
+
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod x11 {
+    use std::ffi::CStr;
+
+    use raw_window_handle::{
+        HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+    };
+    use x11::xlib;
+
+    pub(super) fn system_scale_factor(
+        window: &baseview::Window<'_>,
+        logical_size: baseview::Size,
+    ) -> Option<f64> {
+        let display = match window.raw_display_handle() {
+            RawDisplayHandle::Xlib(handle) if !handle.display.is_null() => {
+                handle.display as *mut xlib::Display
+            }
+            _ => return None,
+        };
+
+        let window = match window.raw_window_handle() {
+            RawWindowHandle::Xlib(handle) => Some(handle.window),
+            _ => None,
+        };
+
+        window
+            .and_then(|window| window_scale_factor(display, window, logical_size))
+            .or_else(|| xft_scale_factor(display))
+    }
+
+    /// Derives the scale factor from the physical size the window has been created with.
+    fn window_scale_factor(
+        display: *mut xlib::Display,
+        window: xlib::Window,
+        logical_size: baseview::Size,
+    ) -> Option<f64> {
+        if logical_size.width <= 0.0 {
+            return None;
+        }
+
+        // SAFETY: `display` and `window` belong to the window that is currently being set up on
+        // this thread, and `XWindowAttributes` is plain old data.
+        let mut attributes: xlib::XWindowAttributes = unsafe { std::mem::zeroed() };
+        let status = unsafe { xlib::XGetWindowAttributes(display, window, &mut attributes) };
+
+        if status == 0 || attributes.width <= 0 {
+            return None;
+        }
+
+        Some(f64::from(attributes.width) / logical_size.width)
+    }
+
+    /// Reads the scale factor from the `Xft.dpi` X resource.
+    fn xft_scale_factor(display: *mut xlib::Display) -> Option<f64> {
+        // SAFETY: `display` is a valid connection, and the returned string is owned by Xlib.
+        let resources = unsafe { xlib::XResourceManagerString(display) };
+
+        if resources.is_null() {
+            return None;
+        }
+
+        let resources = unsafe { CStr::from_ptr(resources) }.to_string_lossy();
+
+        resources
+            .lines()
+            .find_map(|line| line.strip_prefix("Xft.dpi:")?.trim().parse::<f64>().ok())
+            .filter(|dpi| *dpi > 0.0)
+            .map(|dpi| dpi / 96.0)
+    }
+}
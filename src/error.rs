@@ -17,6 +17,23 @@ pub enum Error {
     GraphicsCreationFailed(graphics::Error),
 }
 
+/// A message could not be sent because its window has been closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("the window has been closed")]
+pub struct WindowClosed;
+
+/// A scale factor could not be set on a window.
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum ScaleFactorError {
+    /// The scale factor is not a finite, positive number.
+    #[error("the scale factor {0} is not a finite, positive number")]
+    Invalid(f64),
+
+    /// The window has been closed.
+    #[error(transparent)]
+    WindowClosed(#[from] WindowClosed),
+}
+
 impl From<graphics::Error> for Error {
     fn from(error: iced_graphics::Error) -> Error {
         Error::GraphicsCreationFailed(error)
@@ -0,0 +1,514 @@
+//! Choose your preferred executor to power your application.
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::task::{Context, Poll, Wake, Waker};
+
+use iced_runtime::futures::futures::channel::oneshot;
+use iced_runtime::futures::futures::future::{self, AbortHandle, LocalBoxFuture};
+use iced_runtime::futures::futures::io;
+use iced_runtime::futures::futures::stream::{FuturesUnordered, StreamExt};
+use iced_runtime::futures::MaybeSend;
+use iced_runtime::Command;
+
+pub use iced_runtime::futures::Executor;
+
+use crate::window::{CompletionHold, RuntimeWaker, WindowContext};
+
+/// A default cross-platform executor.
+///
+/// - On native platforms, it will use:
+///   - `iced_futures::backend::native::tokio` when the `tokio` feature is enabled.
+///   - `iced_futures::backend::native::async-std` when the `async-std` feature is
+///     enabled.
+///   - `iced_futures::backend::native::smol` when the `smol` feature is enabled.
+///   - `iced_futures::backend::native::thread_pool` otherwise.
+///
+/// - On Wasm, it will use `iced_futures::backend::wasm::wasm_bindgen`.
+pub type Default = iced_runtime::futures::backend::default::Executor;
+
+/// An [`Executor`] shared by all windows in the process that use the same type of executor.
+///
+/// Creating an executor like a thread pool or a `tokio` runtime is expensive, and a plugin host
+/// may have dozens of editors open at the same time. Using `Shared<executor::Default>` as the
+/// [`Executor`] of an application makes all of its windows use a single executor, which is
+/// created when the first window opens and torn down once the last one has closed.
+///
+/// Every window still keeps track of its own futures, so closing one window only cancels the
+/// futures spawned by that window.
+#[derive(Debug)]
+pub struct Shared<E> {
+    executor: Arc<E>,
+}
+
+impl<E> Executor for Shared<E>
+where
+    E: Executor + Send + Sync + 'static,
+{
+    fn new() -> Result<Self, io::Error> {
+        let mut executors = SHARED_EXECUTORS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // Forget about executors whose windows have all been closed.
+        executors.retain(|(_, executor)| executor.strong_count() > 0);
+
+        let existing = executors
+            .iter()
+            .filter(|(type_id, _)| *type_id == TypeId::of::<E>())
+            .find_map(|(_, executor)| executor.upgrade()?.downcast::<E>().ok());
+
+        let executor = match existing {
+            Some(executor) => executor,
+            None => {
+                let executor = Arc::new(E::new()?);
+                let any: Arc<dyn Any + Send + Sync> = executor.clone();
+                executors.push((TypeId::of::<E>(), Arc::downgrade(&any)));
+
+                executor
+            }
+        };
+
+        Ok(Self { executor })
+    }
+
+    fn spawn(&self, future: impl Future<Output = ()> + MaybeSend + 'static) {
+        self.executor.spawn(future);
+    }
+
+    fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.executor.enter(f)
+    }
+}
+
+/// The executors currently shared between windows, by type.
+static SHARED_EXECUTORS: Mutex<Vec<(TypeId, Weak<dyn Any + Send + Sync>)>> = Mutex::new(Vec::new());
+
+/// Keeps track of the futures a single window has spawned on its [`Executor`], so they can be
+/// cancelled once the window closes. Futures that are still running are aborted when this is
+/// dropped.
+#[derive(Debug)]
+pub(crate) struct Tracked<E> {
+    executor: E,
+    tasks: TrackedTasks,
+}
+
+impl<E> Tracked<E> {
+    /// Returns the futures spawned through this executor, so they can be aborted after the
+    /// executor has been handed to the runtime.
+    pub(crate) fn tasks(&self) -> TrackedTasks {
+        self.tasks.clone()
+    }
+}
+
+/// The futures a window has spawned that are still running.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TrackedTasks(Arc<Mutex<Tasks>>);
+
+#[derive(Debug, Default)]
+struct Tasks {
+    next_id: u64,
+    running: HashMap<u64, AbortHandle>,
+    /// Released once the futures, which keep the tasks alive, have all been dropped.
+    completion: Option<CompletionHold>,
+}
+
+impl TrackedTasks {
+    /// Returns a future that runs `future` until it completes or is aborted through
+    /// [`abort_all`](Self::abort_all).
+    pub(crate) fn track(&self, future: impl Future<Output = ()>) -> impl Future<Output = ()> {
+        let (future, handle) = future::abortable(future);
+
+        let id = {
+            let mut tasks = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            let id = tasks.next_id;
+            tasks.next_id = tasks.next_id.wrapping_add(1);
+            let _ = tasks.running.insert(id, handle);
+
+            id
+        };
+
+        let tasks = self.clone();
+        async move {
+            let _ = future.await;
+
+            let mut tasks = tasks.0.lock().unwrap_or_else(PoisonError::into_inner);
+            let _ = tasks.running.remove(&id);
+        }
+    }
+
+    /// Keeps the window from finishing until these tasks and all of their futures are gone.
+    pub(crate) fn hold_completion(&self, hold: CompletionHold) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .completion = Some(hold);
+    }
+
+    /// Aborts all futures that are still running.
+    pub(crate) fn abort_all(&self) {
+        let mut tasks = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for (_, handle) in tasks.running.drain() {
+            handle.abort();
+        }
+    }
+}
+
+impl<E: Executor> Executor for Tracked<E> {
+    fn new() -> Result<Self, io::Error> {
+        Ok(Self {
+            executor: E::new()?,
+            tasks: TrackedTasks::default(),
+        })
+    }
+
+    fn spawn(&self, future: impl Future<Output = ()> + MaybeSend + 'static) {
+        self.executor.spawn(self.tasks.track(future));
+    }
+
+    fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.executor.enter(f)
+    }
+}
+
+impl<E> Drop for Tracked<E> {
+    fn drop(&mut self) {
+        self.tasks.abort_all();
+    }
+}
+
+/// An executor that runs futures cooperatively on the GUI thread.
+///
+/// Futures are polled by the window whenever they can make progress, so they should never block.
+/// This is useful when commands need to touch state that may only be used from the GUI thread,
+/// like host handles in plugins. Use [`perform_local`] to run futures that are not `Send`.
+///
+/// All windows on a thread share the same set of local futures. Futures are still aborted along
+/// with the window that has spawned them, and are dropped once the last window on the thread has
+/// closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalExecutor;
+
+impl Executor for LocalExecutor {
+    fn new() -> Result<Self, io::Error> {
+        Ok(Self)
+    }
+
+    fn spawn(&self, future: impl Future<Output = ()> + MaybeSend + 'static) {
+        spawn_local(future);
+    }
+}
+
+/// Creates a [`Command`] that runs a future that is not `Send` on the GUI thread, and maps its
+/// result into a message.
+///
+/// This works like `Command::perform`, regardless of the [`Executor`] of the application. Only the
+/// future has to stay on the GUI thread, its result is passed back to the application's executor.
+/// The future starts running on the next frame. This has to be called while the application is
+/// being initialized or updated, so the future can be aborted along with the window's other
+/// futures once it closes.
+pub fn perform_local<T, Message>(
+    future: impl Future<Output = T> + 'static,
+    f: impl FnOnce(T) -> Message + MaybeSend + 'static,
+) -> Command<Message>
+where
+    T: MaybeSend + 'static,
+    Message: 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    let future = async move {
+        let _ = sender.send(future.await);
+    };
+
+    match WindowContext::current() {
+        Some(context) => spawn_local(context.tasks.track(future)),
+        None => {
+            log::warn!(
+                "perform_local was called outside of initializing or updating an application"
+            );
+
+            spawn_local(future);
+        }
+    }
+
+    Command::perform(
+        async move {
+            match receiver.await {
+                Ok(value) => value,
+                // The future has been aborted along with its window, at which point there is
+                // nobody left to receive the message
+                Err(oneshot::Canceled) => future::pending().await,
+            }
+        },
+        f,
+    )
+}
+
+/// Spawns a future on the GUI thread. It will be polled by the windows on this thread.
+pub(crate) fn spawn_local(future: impl Future<Output = ()> + 'static) {
+    LOCAL_POOL.with(|pool| {
+        pool.incoming.borrow_mut().push(Box::pin(future));
+
+        // Make sure the new future gets polled on the next frame.
+        pool.waker.wake_by_ref();
+    });
+}
+
+/// Lets the window owning `waker` poll the futures spawned on this thread. The window is marked
+/// dirty, along with every other window on this thread, whenever one of them can make progress.
+pub(crate) fn register_local(waker: &Arc<RuntimeWaker>) {
+    LOCAL_POOL.with(|pool| pool.waker.register(waker));
+}
+
+/// Stops the window owning `waker` from polling the futures spawned on this thread. Once no
+/// window is left to poll them, the futures are dropped.
+pub(crate) fn release_local(waker: &Arc<RuntimeWaker>) {
+    LOCAL_POOL.with(|pool| {
+        if pool.waker.unregister(waker) > 0 {
+            return;
+        }
+
+        // The futures are dropped outside of the borrows, since dropping them may spawn others.
+        // The pool is in use if the window is closed from within one of its futures, in which
+        // case the next window on the thread takes care of them.
+        loop {
+            let tasks = match pool.tasks.try_borrow_mut() {
+                Ok(mut tasks) => mem::take(&mut *tasks),
+                Err(_) => return,
+            };
+            let incoming = mem::take(&mut *pool.incoming.borrow_mut());
+
+            if tasks.is_empty() && incoming.is_empty() {
+                return;
+            }
+
+            drop(tasks);
+            drop(incoming);
+        }
+    });
+}
+
+/// Polls all futures spawned on this thread that can make progress, see [`register_local`].
+pub(crate) fn poll_local() {
+    LOCAL_POOL.with(|pool| {
+        // A future may open another window, which would then try to poll the same futures.
+        let mut tasks = match pool.tasks.try_borrow_mut() {
+            Ok(tasks) => tasks,
+            Err(_) => return,
+        };
+
+        let pool_waker = Waker::from(pool.waker.clone());
+        let mut context = Context::from_waker(&pool_waker);
+
+        loop {
+            let incoming = mem::take(&mut *pool.incoming.borrow_mut());
+            tasks.extend(incoming);
+
+            match tasks.poll_next_unpin(&mut context) {
+                Poll::Ready(Some(())) => {}
+                Poll::Ready(None) | Poll::Pending => {
+                    // Futures may have spawned other futures while being polled.
+                    if pool.incoming.borrow().is_empty() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+thread_local! {
+    static LOCAL_POOL: LocalPool = LocalPool::default();
+}
+
+#[derive(Default)]
+struct LocalPool {
+    tasks: RefCell<FuturesUnordered<LocalBoxFuture<'static, ()>>>,
+    /// Futures spawned since the pool was last polled. These are kept separately, so futures can
+    /// be spawned while the pool is being polled.
+    incoming: RefCell<Vec<LocalBoxFuture<'static, ()>>>,
+    waker: Arc<PoolWaker>,
+}
+
+/// Wakes every open window on the thread. Any of them may poll the futures, so the futures keep
+/// making progress as long as at least one window on the thread is open.
+#[derive(Debug, Default)]
+struct PoolWaker {
+    windows: Mutex<Vec<Weak<RuntimeWaker>>>,
+}
+
+impl PoolWaker {
+    fn register(&self, waker: &Arc<RuntimeWaker>) {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Forget about windows that have been closed.
+        windows.retain(|window| window.strong_count() > 0);
+
+        if !windows
+            .iter()
+            .any(|window| std::ptr::eq(window.as_ptr(), Arc::as_ptr(waker)))
+        {
+            windows.push(Arc::downgrade(waker));
+        }
+    }
+
+    /// Forgets about the window owning `waker`, and returns the number of windows left.
+    fn unregister(&self, waker: &Arc<RuntimeWaker>) -> usize {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+
+        windows.retain(|window| {
+            window.strong_count() > 0 && !std::ptr::eq(window.as_ptr(), Arc::as_ptr(waker))
+        });
+
+        windows.len()
+    }
+}
+
+impl Wake for PoolWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for window in windows.iter().filter_map(Weak::upgrade) {
+            window.wake_by_ref();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use iced_runtime::futures::futures::future::BoxFuture;
+    use iced_runtime::futures::futures::task::noop_waker;
+
+    thread_local! {
+        /// The futures spawned by [`Queued`] on this thread. Every test runs on its own thread.
+        static QUEUE: RefCell<Vec<BoxFuture<'static, ()>>> = RefCell::new(Vec::new());
+    }
+
+    /// Queues the futures it spawns, so the tests decide when they are polled.
+    #[derive(Debug)]
+    struct Queued;
+
+    impl Executor for Queued {
+        fn new() -> Result<Self, io::Error> {
+            Ok(Self)
+        }
+
+        fn spawn(&self, future: impl Future<Output = ()> + MaybeSend + 'static) {
+            QUEUE.with(|queue| queue.borrow_mut().push(Box::pin(future)));
+        }
+    }
+
+    /// Polls every queued future once, and returns how many of them are still pending.
+    fn poll_queued() -> usize {
+        let mut futures = QUEUE.with(|queue| mem::take(&mut *queue.borrow_mut()));
+
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+        futures.retain_mut(|future| future.as_mut().poll(&mut context).is_pending());
+
+        let pending = futures.len();
+        QUEUE.with(|queue| queue.borrow_mut().extend(futures));
+
+        pending
+    }
+
+    /// Sets its flag once it has been dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Returns a future that never completes, and sets `dropped` once it has been dropped.
+    fn pending_future(dropped: &Arc<AtomicBool>) -> impl Future<Output = ()> + Send + 'static {
+        let flag = DropFlag(dropped.clone());
+
+        async move {
+            let _flag = flag;
+            future::pending::<()>().await;
+        }
+    }
+
+    fn running(tasks: &TrackedTasks) -> usize {
+        tasks
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .running
+            .len()
+    }
+
+    #[test]
+    fn tracked_executors_abort_their_futures_when_dropped() {
+        let executor = Tracked::<Queued>::new().unwrap();
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        executor.spawn(pending_future(&dropped));
+        assert_eq!(poll_queued(), 1);
+
+        drop(executor);
+        assert_eq!(poll_queued(), 0);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn abort_all_aborts_the_running_futures() {
+        let executor = Tracked::<Queued>::new().unwrap();
+        let tasks = executor.tasks.clone();
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        executor.spawn(async {});
+        executor.spawn(pending_future(&dropped));
+        assert_eq!(poll_queued(), 1);
+        assert_eq!(running(&tasks), 1);
+
+        tasks.abort_all();
+        assert_eq!(running(&tasks), 0);
+        assert_eq!(poll_queued(), 0);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    /// How many times [`Counted`] has been created.
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug)]
+    struct Counted;
+
+    impl Executor for Counted {
+        fn new() -> Result<Self, io::Error> {
+            let _ = CREATED.fetch_add(1, Ordering::SeqCst);
+
+            Ok(Self)
+        }
+
+        fn spawn(&self, _future: impl Future<Output = ()> + MaybeSend + 'static) {}
+    }
+
+    #[test]
+    fn shared_executors_are_reused_until_the_last_window_has_closed() {
+        let first = Shared::<Counted>::new().unwrap();
+        let second = Shared::<Counted>::new().unwrap();
+        assert!(Arc::ptr_eq(&first.executor, &second.executor));
+        assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+
+        drop(first);
+        drop(second);
+
+        let _third = Shared::<Counted>::new().unwrap();
+        assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+    }
+}
@@ -24,16 +24,28 @@ pub use iced_runtime::core;
 pub use iced_runtime::futures;
 pub use iced_style as style;
 pub use iced_widget as widget;
+pub use keyboard_types;
 
 mod application;
+mod cleanup;
 pub mod clipboard;
 pub mod conversion;
+pub mod executor;
 pub mod settings;
+pub mod shortcut;
 pub mod window;
 
 #[cfg(feature = "system")]
 pub mod system;
 
+#[cfg(any(feature = "tokio", feature = "async-std", feature = "smol"))]
+pub mod time {
+    //! Listen and react to time.
+    pub use iced_runtime::futures::backend::default::time::*;
+}
+
+mod crash;
+mod dpi;
 mod error;
 mod position;
 mod proxy;
@@ -41,9 +53,11 @@ mod proxy;
 #[cfg(feature = "trace")]
 pub use application::Profiler;
 pub use clipboard::Clipboard;
-pub use error::Error;
+use crash::Crash;
+pub use error::{Error, ScaleFactorError, WindowClosed};
 pub use position::Position;
 pub use proxy::Proxy;
+use runtime::futures::futures::future::BoxFuture;
 use runtime::futures::Executor;
 use runtime::futures::Subscription;
 use runtime::Command;
@@ -53,7 +67,10 @@ pub use iced_graphics::Viewport;
 use style::application::StyleSheet;
 
 pub mod baseview {
-    pub use baseview::{Size, WindowOpenOptions, WindowScalePolicy};
+    pub use baseview::{
+        Event, EventStatus, MouseButton, MouseEvent, Point, ScrollDelta, Size, Window, WindowEvent,
+        WindowHandler, WindowInfo, WindowOpenOptions, WindowScalePolicy,
+    };
 }
 
 use iced_widget::renderer;
@@ -61,23 +78,6 @@ use window::WindowSubs;
 
 pub type Renderer<Theme = style::Theme> = renderer::Renderer<Theme>;
 
-pub mod executor {
-    //! Choose your preferred executor to power your application.
-    pub use iced_runtime::futures::Executor;
-
-    /// A default cross-platform executor.
-    ///
-    /// - On native platforms, it will use:
-    ///   - `iced_futures::backend::native::tokio` when the `tokio` feature is enabled.
-    ///   - `iced_futures::backend::native::async-std` when the `async-std` feature is
-    ///     enabled.
-    ///   - `iced_futures::backend::native::smol` when the `smol` feature is enabled.
-    ///   - `iced_futures::backend::native::thread_pool` otherwise.
-    ///
-    /// - On Wasm, it will use `iced_futures::backend::wasm::wasm_bindgen`.
-    pub type Default = iced_runtime::futures::backend::default::Executor;
-}
-
 pub trait Application: Sized + std::marker::Send {
     /// The [`Executor`] that will run commands and subscriptions.
     ///
@@ -96,6 +96,14 @@ pub trait Application: Sized + std::marker::Send {
     /// The data needed to initialize your [`Application`].
     type Flags: std::marker::Send;
 
+    /// The type of __outputs__ your [`Application`] emits to the owner of its window with
+    /// [`window::emit`], e.g. to ask a plugin to load a preset. They are received through the
+    /// [`WindowHandle`](window::WindowHandle) returned by [`open_parented`], or returned by
+    /// [`open_blocking_with_result`] once the window has closed.
+    ///
+    /// Use `()` if the [`Application`] does not need to talk to its owner.
+    type Output: std::marker::Send + 'static;
+
     /// Initializes the [`Application`] with the flags provided to
     /// [`run`] as part of the [`Settings`].
     ///
@@ -129,6 +137,19 @@ pub trait Application: Sized + std::marker::Send {
     /// These widgets can produce __messages__ based on user interaction.
     fn view(&self) -> Element<'_, Self::Message, crate::Renderer<Self::Theme>>;
 
+    /// Returns the widgets to display instead of [`view`](Self::view) once the [`Application`]
+    /// has panicked in [`update`](Self::update) or [`view`](Self::view).
+    ///
+    /// The panic is caught so it does not unwind into the host. Since the state of the
+    /// [`Application`] may be inconsistent afterwards, it is not updated anymore.
+    ///
+    /// By default, this shows the panic message in the center of the window.
+    fn crashed_view(
+        message: &str,
+    ) -> Element<'static, Self::Message, crate::Renderer<Self::Theme>> {
+        crash::crashed_view(message)
+    }
+
     /// Returns the current [`Theme`] of the [`Application`].
     ///
     /// [`Theme`]: Self::Theme
@@ -167,12 +188,77 @@ pub trait Application: Sized + std::marker::Send {
         baseview::WindowScalePolicy::SystemScaleFactor
     }
 
+    /// Returns the logical size the [`Application`] is designed at, if it should be scaled to fit
+    /// the window.
+    ///
+    /// When this returns a size, the user interface is always laid out at that size, overriding
+    /// [`scale_policy`](Self::scale_policy). The scale factor is derived from the size of the
+    /// window instead, and the user interface is centered with the theme's background color
+    /// filling any remaining space when the aspect ratios differ.
+    ///
+    /// By default, it returns `None`.
+    fn scale_to_fit(&self) -> Option<baseview::Size> {
+        None
+    }
+
+    /// Returns a future to run when the window closes, e.g. to flush state to disk.
+    ///
+    /// By the time this is called, all commands and subscriptions of the [`Application`] have
+    /// been cancelled, including any spawned while handling the close message.
+    ///
+    /// The window does not wait for the future, so closing it never blocks the host. Instead, the
+    /// future runs on a single worker thread shared by all windows, and is dropped if it has not
+    /// completed after [`cleanup_timeout`]. [`WindowHandle::join`] waits for it either way.
+    ///
+    /// The future is created within the [`Executor`]'s context, but not polled by the executor,
+    /// which shuts down along with the window. The future can therefore not rely on the executor,
+    /// e.g. on `tokio`'s timers, nor on futures running on the GUI thread.
+    ///
+    /// By default, it returns `None`.
+    ///
+    /// [`cleanup_timeout`]: crate::settings::IcedBaseviewSettings::cleanup_timeout
+    /// [`WindowHandle::join`]: crate::window::WindowHandle::join
+    fn cleanup(&mut self) -> Option<BoxFuture<'static, ()>> {
+        None
+    }
+
+    /// Returns the keyboard [`Shortcut`]s of the [`Application`] in its current state.
+    ///
+    /// Shortcuts match the logical key the user presses, so they follow the keyboard layout. Key
+    /// presses go to the widgets first. Only when none of them has captured a key press, e.g.
+    /// because no text input is focused, its shortcut's message is sent to
+    /// [`update`](Self::update).
+    ///
+    /// By default, it returns no shortcuts.
+    ///
+    /// [`Shortcut`]: shortcut::Shortcut
+    fn shortcuts(&self) -> Vec<shortcut::Shortcut<Self::Message>> {
+        Vec::new()
+    }
+
     fn renderer_settings() -> renderer::Settings {
         Default::default()
     }
 }
 
-struct Instance<A: Application>(A);
+/// The [`Application`] along with whether it has panicked. The application is `None` if
+/// [`Application::new`] has panicked.
+struct Instance<A: Application>(Option<A>, Crash);
+
+impl<A: Application> Instance<A> {
+    /// Runs `f` on the application, catching any panic. A crashed application is not run
+    /// anymore, so it does not receive any more messages either.
+    fn catch<T>(&self, f: impl FnOnce(&A) -> T) -> Option<T> {
+        self.1.catch(|| self.0.as_ref().map(f)).flatten()
+    }
+
+    /// Like [`catch`](Self::catch), but with mutable access to the application.
+    fn catch_mut<T>(&mut self, f: impl FnOnce(&mut A) -> T) -> Option<T> {
+        let application = &mut self.0;
+
+        self.1.catch(|| application.as_mut().map(f)).flatten()
+    }
+}
 
 impl<A> crate::runtime::Program for Instance<A>
 where
@@ -182,11 +268,18 @@ where
     type Message = A::Message;
 
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
-        self.0.update(message)
+        self.catch_mut(|application| application.update(message))
+            .unwrap_or_else(Command::none)
     }
 
     fn view(&self) -> Element<'_, Self::Message, Self::Renderer> {
-        self.0.view()
+        // The element borrows the application, so this cannot go through `catch`
+        let application = self.0.as_ref();
+
+        match self.1.catch(|| application.map(A::view)).flatten() {
+            Some(element) => element,
+            None => A::crashed_view(&self.1.message().unwrap_or_default()),
+        }
     }
 }
 
@@ -195,34 +288,56 @@ where
     A: Application,
 {
     type Flags = A::Flags;
+    type Output = A::Output;
 
     fn new(flags: Self::Flags) -> (Self, Command<A::Message>) {
-        let (app, command) = A::new(flags);
+        let crash = Crash::default();
 
-        (Instance(app), command)
+        match crash.catch(|| A::new(flags)) {
+            Some((application, command)) => (Instance(Some(application), crash), command),
+            None => (Instance(None, crash), Command::none()),
+        }
     }
 
     fn title(&self) -> String {
-        self.0.title()
+        self.catch(A::title).unwrap_or_default()
     }
 
     fn theme(&self) -> A::Theme {
-        self.0.theme()
+        self.catch(A::theme).unwrap_or_default()
     }
 
     fn style(&self) -> <A::Theme as StyleSheet>::Style {
-        self.0.style()
+        self.catch(A::style).unwrap_or_default()
     }
 
     fn subscription(
         &self,
         window_subs: &mut WindowSubs<A::Message>,
     ) -> runtime::futures::Subscription<Self::Message> {
-        self.0.subscription(window_subs)
+        self.catch(|application| application.subscription(window_subs))
+            .unwrap_or_else(Subscription::none)
     }
 
     fn scale_policy(&self) -> baseview::WindowScalePolicy {
-        self.0.scale_policy()
+        self.catch(A::scale_policy)
+            .unwrap_or(baseview::WindowScalePolicy::SystemScaleFactor)
+    }
+
+    fn scale_to_fit(&self) -> Option<baseview::Size> {
+        self.catch(A::scale_to_fit).flatten()
+    }
+
+    fn cleanup(&mut self) -> Option<BoxFuture<'static, ()>> {
+        self.catch_mut(A::cleanup).flatten()
+    }
+
+    fn shortcuts(&self) -> Vec<shortcut::Shortcut<A::Message>> {
+        self.catch(A::shortcuts).unwrap_or_default()
+    }
+
+    fn take_panic(&self) -> Option<String> {
+        self.1.take_unreported()
     }
 
     fn renderer_settings() -> crate::renderer::Settings {
@@ -234,7 +349,7 @@ where
 pub fn open_parented<A, P>(
     parent: &P,
     settings: Settings<A::Flags>,
-) -> window::WindowHandle<A::Message>
+) -> window::WindowHandle<A::Message, A::Output>
 where
     A: Application + 'static,
     P: raw_window_handle::HasRawWindowHandle,
@@ -255,3 +370,25 @@ where
         settings,
     );
 }
+
+/// Runs the [`Application`] like [`open_blocking`], and returns its final state once the window
+/// has been closed, together with the outputs it has emitted with [`window::emit`].
+///
+/// This lets standalone tools report what the user chose. The state is `None` if the application
+/// did not shut down through its regular close path, while the outputs are returned either way.
+///
+/// * `settings` - The settings of the window.
+pub fn open_blocking_with_result<A>(settings: Settings<A::Flags>) -> (Option<A>, Vec<A::Output>)
+where
+    A: Application + 'static,
+{
+    let (application, outputs) = window::IcedWindow::<Instance<A>>::open_blocking_with_result::<
+        A::Executor,
+        renderer::Compositor<A::Theme>,
+    >(settings);
+
+    (
+        application.and_then(|Instance(application, _)| application),
+        outputs,
+    )
+}
@@ -1,7 +1,8 @@
 //! Configure your application.
-use std::{borrow::Cow, fmt::Debug};
+use std::{borrow::Cow, fmt::Debug, sync::Arc, time::Duration};
 
-use baseview::WindowOpenOptions;
+use baseview::{Size, WindowOpenOptions};
+use keyboard_types::{Code, KeyState, KeyboardEvent, Modifiers};
 
 /// The settings of an application.
 pub struct Settings<Flags> {
@@ -25,8 +26,11 @@ pub struct Settings<Flags> {
     pub fonts: Vec<Cow<'static, [u8]>>,
 }
 
+/// A function that is called with the message of a panic that was caught in the application.
+pub type PanicHook = Arc<dyn Fn(&str) + Send + Sync>;
+
 /// Any settings specific to `iced_baseview`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone)]
 pub struct IcedBaseviewSettings {
     /// Ignore key inputs, except for modifier keys such as SHIFT and ALT
     pub ignore_non_modifier_keys: bool,
@@ -37,4 +41,424 @@ pub struct IcedBaseviewSettings {
     /// reopening the editor) and an iced limitation where it's not possible to have animations
     /// without using an asynchronous timer stream to send redraw messages to the application.
     pub always_redraw: bool,
+
+    /// Constraints on the size of the window. Resize requests from the host and the user are
+    /// adjusted to fit these constraints.
+    pub size_constraints: SizeConstraints,
+
+    /// Bind Ctrl+= or Ctrl++ (zoom in), Ctrl+- (zoom out) and Ctrl+0 (reset zoom) to the window's
+    /// zoom factor. Cmd is used in place of Ctrl on macOS. The shortcuts follow the keyboard
+    /// layout, only apply while the application uses `WindowScalePolicy::SystemScaleFactor`, and
+    /// leave key presses that a widget has captured alone.
+    pub zoom_shortcuts: bool,
+
+    /// How long the future returned by [`Application::cleanup`] may run on the cleanup thread
+    /// after the window has closed, before it is dropped.
+    ///
+    /// [`Application::cleanup`]: crate::Application::cleanup
+    pub cleanup_timeout: Duration,
+
+    /// Only update and redraw the window when the host calls [`WindowHandle::pump`], instead of
+    /// on every frame. Ignored by blocking windows, which have no handle to pump them with.
+    ///
+    /// [`WindowHandle::pump`]: crate::window::WindowHandle::pump
+    pub manual_pump: bool,
+
+    /// Keyboard shortcuts that close the window, like Cmd+Q on macOS. Unlike the window's close
+    /// button, these can be vetoed with [`WindowSubs::on_close_requested`].
+    ///
+    /// [`WindowSubs::on_close_requested`]: crate::window::WindowSubs::on_close_requested
+    pub exit_shortcuts: Cow<'static, [ExitShortcut]>,
+
+    /// Called with the panic message when the application panics. The panic is caught so it does
+    /// not unwind into the host, and the application shows its
+    /// [`crashed_view`](crate::Application::crashed_view) from then on. Plugin wrappers can use
+    /// this to report the crash.
+    pub panic_hook: Option<PanicHook>,
+}
+
+impl Debug for IcedBaseviewSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IcedBaseviewSettings")
+            .field("ignore_non_modifier_keys", &self.ignore_non_modifier_keys)
+            .field("always_redraw", &self.always_redraw)
+            .field("size_constraints", &self.size_constraints)
+            .field("zoom_shortcuts", &self.zoom_shortcuts)
+            .field("cleanup_timeout", &self.cleanup_timeout)
+            .field("manual_pump", &self.manual_pump)
+            .field("exit_shortcuts", &self.exit_shortcuts)
+            .field("panic_hook", &self.panic_hook.as_ref().map(|_| "Fn(&str)"))
+            .finish()
+    }
+}
+
+impl Default for IcedBaseviewSettings {
+    fn default() -> Self {
+        Self {
+            ignore_non_modifier_keys: false,
+            always_redraw: false,
+            size_constraints: SizeConstraints::default(),
+            zoom_shortcuts: false,
+            cleanup_timeout: Duration::from_secs(1),
+            manual_pump: false,
+            exit_shortcuts: Cow::Borrowed(ExitShortcut::DEFAULT),
+            panic_hook: None,
+        }
+    }
+}
+
+/// The modifiers keyboard shortcuts are compared on. Others, like Caps Lock, are ignored.
+pub(crate) const SHORTCUT_MODIFIERS: Modifiers = Modifiers::ALT
+    .union(Modifiers::CONTROL)
+    .union(Modifiers::SHIFT)
+    .union(Modifiers::META);
+
+/// A keyboard shortcut that closes the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitShortcut {
+    /// The physical key that has to be pressed.
+    pub code: Code,
+
+    /// The modifiers that have to be held, and no others.
+    pub modifiers: Modifiers,
+}
+
+impl ExitShortcut {
+    /// Cmd+Q, the usual way to quit an application on macOS.
+    pub const CMD_Q: Self = Self {
+        code: Code::KeyQ,
+        modifiers: Modifiers::META,
+    };
+
+    /// Alt+F4, the usual way to close a window on Windows and Linux.
+    pub const ALT_F4: Self = Self {
+        code: Code::F4,
+        modifiers: Modifiers::ALT,
+    };
+
+    /// The exit shortcuts used by default. This is Cmd+Q on macOS, and nothing on other
+    /// platforms, where the window manager takes care of Alt+F4.
+    #[cfg(target_os = "macos")]
+    pub const DEFAULT: &'static [Self] = &[Self::CMD_Q];
+    /// The exit shortcuts used by default. This is Cmd+Q on macOS, and nothing on other
+    /// platforms, where the window manager takes care of Alt+F4.
+    #[cfg(not(target_os = "macos"))]
+    pub const DEFAULT: &'static [Self] = &[];
+
+    /// Returns `true` if `event` presses this shortcut.
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        event.state == KeyState::Down
+            && !event.repeat
+            && event.code == self.code
+            && event.modifiers & SHORTCUT_MODIFIERS == self.modifiers & SHORTCUT_MODIFIERS
+    }
+}
+
+/// Constraints on the logical size of a window.
+///
+/// Plugin wrappers can use these to answer the host's resize queries, such as CLAP's
+/// `gui.can_resize` and `gui.adjust_size`, or VST3's `canResize` and `checkSizeConstraint`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeConstraints {
+    /// Whether the window may be resized at all.
+    pub resizable: bool,
+
+    /// The minimum logical size of the window.
+    pub min_size: Option<Size>,
+
+    /// The maximum logical size of the window.
+    pub max_size: Option<Size>,
+
+    /// The aspect ratio (width divided by height) the window has to keep.
+    pub aspect_ratio: Option<f64>,
+}
+
+impl SizeConstraints {
+    /// Constraints for a window that cannot be resized.
+    pub fn fixed() -> Self {
+        Self {
+            resizable: false,
+            ..Default::default()
+        }
+    }
+
+    /// Returns `true` if the window may be resized.
+    pub fn can_resize(&self) -> bool {
+        self.resizable
+    }
+
+    /// Returns the size closest to `size` that satisfies the minimum size, maximum size and
+    /// aspect ratio constraints, regardless of whether the window is resizable.
+    ///
+    /// The size is never smaller than one logical pixel. Constraints that cannot be satisfied are
+    /// ignored: minimum sizes that are not finite, maximum sizes that are not positive, and aspect
+    /// ratios that are not finite and positive.
+    pub fn constrain(&self, size: Size) -> Size {
+        let min_size = self.min_size.map_or(Size::new(1.0, 1.0), |min_size| {
+            Size::new(valid_min(min_size.width), valid_min(min_size.height))
+        });
+        let max_size = self
+            .max_size
+            .map_or(Size::new(f64::INFINITY, f64::INFINITY), |max_size| {
+                Size::new(
+                    valid_max(max_size.width).max(min_size.width),
+                    valid_max(max_size.height).max(min_size.height),
+                )
+            });
+        let size = Size::new(valid_request(size.width), valid_request(size.height));
+
+        let clamped = Size::new(
+            size.width.max(min_size.width).min(max_size.width),
+            size.height.max(min_size.height).min(max_size.height),
+        );
+
+        match self
+            .aspect_ratio
+            .filter(|aspect_ratio| aspect_ratio.is_finite() && *aspect_ratio > 0.0)
+        {
+            Some(aspect_ratio) => {
+                // Fit the largest size with the right aspect ratio into the requested size, and
+                // then scale it uniformly to respect the minimum and maximum sizes.
+                let width = size.width.min(size.height * aspect_ratio);
+                let height = width / aspect_ratio;
+
+                let grow = (min_size.width / width)
+                    .max(min_size.height / height)
+                    .max(1.0);
+                let shrink = (max_size.width / (width * grow))
+                    .min(max_size.height / (height * grow))
+                    .min(1.0);
+
+                let constrained = Size::new(width * grow * shrink, height * grow * shrink);
+
+                // Extreme aspect ratios can still overflow, in which case the ratio is dropped
+                if is_valid_size(constrained) {
+                    constrained
+                } else {
+                    clamped
+                }
+            }
+            None => clamped,
+        }
+    }
+
+    /// Returns the size the window should have when `size` is requested while the window
+    /// currently has `current_size`.
+    pub fn check_size(&self, size: Size, current_size: Size) -> Size {
+        if self.resizable {
+            self.constrain(size)
+        } else {
+            current_size
+        }
+    }
+}
+
+impl Default for SizeConstraints {
+    fn default() -> Self {
+        Self {
+            resizable: true,
+            min_size: None,
+            max_size: None,
+            aspect_ratio: None,
+        }
+    }
+}
+
+/// Returns a minimum size component of at least one logical pixel.
+fn valid_min(min: f64) -> f64 {
+    if min.is_finite() {
+        min.max(1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Returns a maximum size component, or infinity if it cannot be satisfied.
+fn valid_max(max: f64) -> f64 {
+    if max > 0.0 {
+        max
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Returns a requested size component between one logical pixel and `f64::MAX`.
+fn valid_request(size: f64) -> f64 {
+    if size.is_nan() {
+        1.0
+    } else {
+        size.clamp(1.0, f64::MAX)
+    }
+}
+
+fn is_valid_size(size: Size) -> bool {
+    size.width.is_finite() && size.width > 0.0 && size.height.is_finite() && size.height > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_size(size: Size, width: f64, height: f64) {
+        assert!(
+            (size.width - width).abs() < 1e-9 && (size.height - height).abs() < 1e-9,
+            "expected {}x{}, got {}x{}",
+            width,
+            height,
+            size.width,
+            size.height
+        );
+    }
+
+    fn constraints(
+        min_size: Option<(f64, f64)>,
+        max_size: Option<(f64, f64)>,
+        aspect_ratio: Option<f64>,
+    ) -> SizeConstraints {
+        SizeConstraints {
+            resizable: true,
+            min_size: min_size.map(|(width, height)| Size::new(width, height)),
+            max_size: max_size.map(|(width, height)| Size::new(width, height)),
+            aspect_ratio,
+        }
+    }
+
+    #[test]
+    fn constrain_clamps_to_min_and_max_size() {
+        let constraints = constraints(Some((200.0, 100.0)), Some((800.0, 600.0)), None);
+
+        assert_size(constraints.constrain(Size::new(400.0, 300.0)), 400.0, 300.0);
+        assert_size(constraints.constrain(Size::new(100.0, 50.0)), 200.0, 100.0);
+        assert_size(
+            constraints.constrain(Size::new(1000.0, 1000.0)),
+            800.0,
+            600.0,
+        );
+    }
+
+    #[test]
+    fn constrain_keeps_aspect_ratio() {
+        let constraints = constraints(Some((200.0, 100.0)), Some((800.0, 400.0)), Some(2.0));
+
+        assert_size(constraints.constrain(Size::new(600.0, 600.0)), 600.0, 300.0);
+        assert_size(constraints.constrain(Size::new(100.0, 100.0)), 200.0, 100.0);
+        assert_size(
+            constraints.constrain(Size::new(2000.0, 2000.0)),
+            800.0,
+            400.0,
+        );
+    }
+
+    #[test]
+    fn constrain_ignores_invalid_aspect_ratios() {
+        for aspect_ratio in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let constraints = constraints(None, None, Some(aspect_ratio));
+
+            assert_size(constraints.constrain(Size::new(400.0, 300.0)), 400.0, 300.0);
+        }
+    }
+
+    #[test]
+    fn constrain_never_returns_zero_or_nan() {
+        let requests = [
+            Size::new(0.0, 0.0),
+            Size::new(400.0, 0.0),
+            Size::new(-10.0, 300.0),
+            Size::new(f64::NAN, f64::NAN),
+            Size::new(f64::INFINITY, 300.0),
+        ];
+        let all_constraints = [
+            constraints(None, None, None),
+            constraints(None, None, Some(1.5)),
+            constraints(Some((0.0, 0.0)), Some((0.0, 0.0)), Some(1.5)),
+            constraints(Some((f64::NAN, -1.0)), Some((f64::NAN, -1.0)), Some(1.5)),
+            constraints(Some((100.0, 100.0)), Some((50.0, 50.0)), None),
+            constraints(None, None, Some(f64::MIN_POSITIVE)),
+            constraints(None, None, Some(f64::MAX)),
+        ];
+
+        for constraints in &all_constraints {
+            for request in requests {
+                let size = constraints.constrain(request);
+
+                assert!(
+                    is_valid_size(size) && size.width >= 1.0 && size.height >= 1.0,
+                    "{:?} constrained {}x{} to {}x{}",
+                    constraints,
+                    request.width,
+                    request.height,
+                    size.width,
+                    size.height
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn constrain_prefers_min_size_over_conflicting_max_size() {
+        let constraints = constraints(Some((300.0, 200.0)), Some((100.0, 100.0)), None);
+
+        assert_size(constraints.constrain(Size::new(50.0, 50.0)), 300.0, 200.0);
+    }
+
+    #[test]
+    fn check_size_keeps_current_size_when_not_resizable() {
+        let constraints = SizeConstraints {
+            min_size: Some(Size::new(200.0, 100.0)),
+            ..SizeConstraints::fixed()
+        };
+
+        assert_size(
+            constraints.check_size(Size::new(1000.0, 1000.0), Size::new(400.0, 300.0)),
+            400.0,
+            300.0,
+        );
+    }
+
+    #[test]
+    fn check_size_constrains_when_resizable() {
+        let constraints = constraints(Some((200.0, 100.0)), None, Some(2.0));
+
+        assert_size(
+            constraints.check_size(Size::new(100.0, 100.0), Size::new(400.0, 300.0)),
+            200.0,
+            100.0,
+        );
+    }
+
+    fn code_down(code: Code, modifiers: Modifiers) -> KeyboardEvent {
+        KeyboardEvent {
+            state: KeyState::Down,
+            code,
+            modifiers,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn exit_shortcuts_ignore_lock_keys() {
+        let event = code_down(
+            Code::KeyQ,
+            Modifiers::META | Modifiers::CAPS_LOCK | Modifiers::NUM_LOCK,
+        );
+
+        assert!(ExitShortcut::CMD_Q.matches(&event));
+    }
+
+    #[test]
+    fn exit_shortcuts_require_their_modifiers_only() {
+        assert!(!ExitShortcut::CMD_Q.matches(&code_down(Code::KeyQ, Modifiers::empty())));
+        assert!(!ExitShortcut::CMD_Q
+            .matches(&code_down(Code::KeyQ, Modifiers::META | Modifiers::SHIFT)));
+    }
+
+    #[test]
+    fn exit_shortcuts_ignore_repeats() {
+        let event = KeyboardEvent {
+            repeat: true,
+            ..code_down(Code::F4, Modifiers::ALT)
+        };
+
+        assert!(!ExitShortcut::ALT_F4.matches(&event));
+    }
 }
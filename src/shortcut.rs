@@ -0,0 +1,193 @@
+//! Keyboard shortcuts that follow the keyboard layout.
+//!
+//! Unlike iced's [`KeyCode`](crate::core::keyboard::KeyCode)s, which describe the physical
+//! position of a key, a [`Shortcut`] matches the logical [`Key`] the user has pressed. Ctrl+Z
+//! stays Ctrl+Z on a German keyboard, where the Z key sits where US keyboards have their Y key.
+use keyboard_types::{Key, KeyState, KeyboardEvent, Modifiers};
+
+use crate::settings::SHORTCUT_MODIFIERS;
+
+/// A keyboard shortcut that produces a message, see
+/// [`Application::shortcuts`](crate::Application::shortcuts).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortcut<Message> {
+    /// The logical key that has to be pressed.
+    pub key: Key,
+
+    /// The modifiers that have to be held, and no others.
+    pub modifiers: Modifiers,
+
+    /// The message to produce when the shortcut is pressed.
+    pub message: Message,
+}
+
+impl<Message> Shortcut<Message> {
+    /// Creates a new [`Shortcut`] for a logical key, e.g. `Key::Enter`.
+    pub fn new(modifiers: Modifiers, key: Key, message: Message) -> Self {
+        Self {
+            key,
+            modifiers,
+            message,
+        }
+    }
+
+    /// Creates a new [`Shortcut`] for a key that writes a character, e.g.
+    /// `Shortcut::character(Modifiers::CONTROL, "z", Message::Undo)`.
+    pub fn character(modifiers: Modifiers, character: &str, message: Message) -> Self {
+        Self::new(modifiers, Key::Character(character.to_owned()), message)
+    }
+
+    /// Returns `true` if `event` presses this shortcut.
+    ///
+    /// Characters are compared case-insensitively, so holding Shift is only required if the
+    /// shortcut asks for it. Shift is ignored for characters without case, since it may be needed
+    /// to type them at all, e.g. "+" on a US keyboard. Characters typed with AltGr are matched on
+    /// the character alone, even though some platforms report AltGr as Ctrl+Alt.
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        if event.state != KeyState::Down {
+            return false;
+        }
+
+        let mut modifiers = event.modifiers;
+        if modifiers.contains(Modifiers::ALT_GRAPH) {
+            modifiers.remove(Modifiers::CONTROL | Modifiers::ALT);
+        }
+        let mut modifiers = modifiers & SHORTCUT_MODIFIERS;
+        let mut expected = self.modifiers & SHORTCUT_MODIFIERS;
+
+        match (&self.key, &event.key) {
+            (Key::Character(shortcut), Key::Character(pressed)) => {
+                let shortcut = shortcut.to_lowercase();
+                if shortcut != pressed.to_lowercase() {
+                    return false;
+                }
+
+                if shortcut == shortcut.to_uppercase() {
+                    modifiers.remove(Modifiers::SHIFT);
+                    expected.remove(Modifiers::SHIFT);
+                }
+            }
+            (shortcut, pressed) => {
+                if shortcut != pressed {
+                    return false;
+                }
+            }
+        }
+
+        modifiers == expected
+    }
+}
+
+/// Returns the message of the first shortcut `event` presses, if any.
+pub(crate) fn find_message<Message>(
+    shortcuts: Vec<Shortcut<Message>>,
+    event: &KeyboardEvent,
+) -> Option<Message> {
+    shortcuts
+        .into_iter()
+        .find(|shortcut| shortcut.matches(event))
+        .map(|shortcut| shortcut.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_down(key: Key, modifiers: Modifiers) -> KeyboardEvent {
+        KeyboardEvent {
+            state: KeyState::Down,
+            key,
+            modifiers,
+            ..Default::default()
+        }
+    }
+
+    fn character_down(character: &str, modifiers: Modifiers) -> KeyboardEvent {
+        key_down(Key::Character(character.to_owned()), modifiers)
+    }
+
+    #[test]
+    fn characters_ignore_case() {
+        let undo = Shortcut::character(Modifiers::CONTROL, "z", ());
+
+        assert!(undo.matches(&character_down("z", Modifiers::CONTROL)));
+        assert!(undo.matches(&character_down("Z", Modifiers::CONTROL)));
+        assert!(!undo.matches(&character_down("y", Modifiers::CONTROL)));
+    }
+
+    #[test]
+    fn shift_is_only_required_if_asked_for() {
+        let undo = Shortcut::character(Modifiers::CONTROL, "z", ());
+        let redo = Shortcut::character(Modifiers::CONTROL | Modifiers::SHIFT, "z", ());
+        let ctrl_shift = Modifiers::CONTROL | Modifiers::SHIFT;
+
+        assert!(!undo.matches(&character_down("Z", ctrl_shift)));
+        assert!(redo.matches(&character_down("Z", ctrl_shift)));
+        assert!(!redo.matches(&character_down("z", Modifiers::CONTROL)));
+    }
+
+    #[test]
+    fn shift_is_ignored_for_characters_without_case() {
+        let zoom_in = Shortcut::character(Modifiers::CONTROL, "+", ());
+
+        assert!(zoom_in.matches(&character_down("+", Modifiers::CONTROL)));
+        assert!(zoom_in.matches(&character_down("+", Modifiers::CONTROL | Modifiers::SHIFT)));
+    }
+
+    #[test]
+    fn shift_is_compared_for_named_keys() {
+        let next = Shortcut::new(Modifiers::empty(), Key::Tab, ());
+
+        assert!(next.matches(&key_down(Key::Tab, Modifiers::empty())));
+        assert!(!next.matches(&key_down(Key::Tab, Modifiers::SHIFT)));
+    }
+
+    #[test]
+    fn alt_graph_is_matched_on_the_character() {
+        let at = Shortcut::character(Modifiers::empty(), "@", ());
+
+        // Some platforms report AltGr as Ctrl+Alt
+        let alt_graph = Modifiers::ALT_GRAPH | Modifiers::CONTROL | Modifiers::ALT;
+        assert!(at.matches(&character_down("@", alt_graph)));
+        assert!(at.matches(&character_down("@", Modifiers::ALT_GRAPH)));
+
+        // Without AltGr, Ctrl+Alt is compared as usual
+        assert!(!at.matches(&character_down("@", Modifiers::CONTROL | Modifiers::ALT)));
+    }
+
+    #[test]
+    fn other_modifiers_have_to_match_exactly() {
+        let reset_zoom = Shortcut::character(Modifiers::CONTROL, "0", ());
+
+        assert!(reset_zoom.matches(&character_down(
+            "0",
+            Modifiers::CONTROL | Modifiers::CAPS_LOCK
+        )));
+        assert!(!reset_zoom.matches(&character_down("0", Modifiers::CONTROL | Modifiers::ALT)));
+        assert!(!reset_zoom.matches(&character_down("0", Modifiers::empty())));
+    }
+
+    #[test]
+    fn key_releases_do_not_match() {
+        let undo = Shortcut::character(Modifiers::CONTROL, "z", ());
+        let event = KeyboardEvent {
+            state: KeyState::Up,
+            ..character_down("z", Modifiers::CONTROL)
+        };
+
+        assert!(!undo.matches(&event));
+    }
+
+    #[test]
+    fn find_message_returns_the_first_match() {
+        let shortcuts = vec![
+            Shortcut::character(Modifiers::CONTROL, "z", 1),
+            Shortcut::character(Modifiers::CONTROL, "Z", 2),
+        ];
+
+        assert_eq!(
+            find_message(shortcuts, &character_down("z", Modifiers::CONTROL)),
+            Some(1)
+        );
+    }
+}
@@ -1,14 +1,37 @@
-use std::{cell::RefCell, pin::Pin, rc::Rc, sync::Arc};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::VecDeque,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex, PoisonError, Weak,
+    },
+    task::{Context, Poll, Wake, Waker},
+    time::{Duration, Instant},
+};
 
-use baseview::{Event, EventStatus, Window, WindowHandler, WindowOpenOptions};
+use baseview::{Event, EventStatus, Window, WindowHandler, WindowInfo, WindowOpenOptions};
+use iced_runtime::core::Rectangle;
 use iced_runtime::futures::futures::{
     self,
     channel::mpsc::{self, SendError},
+    StreamExt,
 };
+use iced_runtime::Command;
 use iced_style::application::StyleSheet;
 use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 
-use crate::{application::run, application::Application, Settings};
+use crate::{
+    application::run,
+    application::Application,
+    executor::TrackedTasks,
+    settings::{PanicHook, SizeConstraints},
+    shortcut::Shortcut,
+    ScaleFactorError, Settings, WindowClosed,
+};
 
 pub enum RuntimeEvent<Message: 'static + Send> {
     Baseview((baseview::Event, bool)),
@@ -16,6 +39,12 @@ pub enum RuntimeEvent<Message: 'static + Send> {
     MainEventsCleared,
     RedrawRequested,
     WillClose,
+    /// The host wants the window to be resized to the given logical size.
+    Resize(baseview::Size),
+    /// The host wants the window to use the given scale factor.
+    SetScaleFactor(f64),
+    /// The owner of the window forwards a text composition from an input method.
+    Composition(Composition),
 }
 
 pub struct IcedWindow<A>
@@ -26,11 +55,28 @@ where
     // C: window::Compositor<Renderer = A::Renderer> + 'static,
 {
     pub sender: mpsc::UnboundedSender<RuntimeEvent<A::Message>>,
-    pub instance: Pin<Box<dyn futures::Future<Output = ()>>>,
-    pub runtime_context: futures::task::Context<'static>,
+    pub instance: Pin<Box<dyn futures::Future<Output = A>>>,
+    /// Marks the window as dirty whenever the runtime or a future on the GUI thread can make
+    /// progress, or the window has received an event.
+    pub waker: Arc<RuntimeWaker>,
+    /// Marks the window as dirty whenever the instance can make progress, e.g. because it has
+    /// asked for another frame.
+    pub instance_waker: Arc<RuntimeWaker>,
+    /// Called when a panic escapes the instance.
+    pub panic_hook: Option<PanicHook>,
+    /// Only do the work of a frame after the host has asked for it through
+    /// [`WindowHandle::pump`].
+    pub manual_pump: bool,
     pub runtime_rx: mpsc::UnboundedReceiver<A::Message>,
     pub window_queue_rx: mpsc::UnboundedReceiver<WindowQueueMessage>,
     pub event_status: Rc<RefCell<EventStatus>>,
+    /// Receives the final state of the application once the instance has finished.
+    pub exit_state: Option<Arc<Mutex<Option<A>>>>,
+    /// The state the window shares with its [`WindowHandle`].
+    pub(crate) shared: Arc<WindowShared<A::Output>>,
+    /// Keeps the window from finishing until it has been dropped, along with the application and
+    /// everything else the instance owns.
+    pub(crate) completion: CompletionHold,
 
     pub processed_close_signal: bool,
 }
@@ -52,20 +98,67 @@ where
     /// Open a new window that blocks the current thread until the window is destroyed.
     ///
     /// * `settings` - The settings of the window.
-    pub fn open_blocking<E, C>(#[allow(unused_mut)] mut settings: Settings<A::Flags>)
+    pub fn open_blocking<E, C>(settings: Settings<A::Flags>)
+    where
+        E: iced_runtime::futures::Executor + 'static,
+        C: iced_graphics::Compositor<Renderer = A::Renderer, Settings = crate::renderer::Settings>
+            + 'static,
+    {
+        let _ = Self::open_blocking_with_result::<E, C>(settings);
+    }
+
+    /// Open a new window that blocks the current thread until the window is destroyed, and
+    /// return the final state of the application together with the outputs it has emitted.
+    ///
+    /// The state is `None` if the application did not shut down through its regular close path.
+    /// The outputs are returned either way, in the order they were emitted.
+    ///
+    /// [`manual_pump`](crate::settings::IcedBaseviewSettings::manual_pump) is ignored, since
+    /// there is no [`WindowHandle`] to pump the window with.
+    ///
+    /// * `settings` - The settings of the window.
+    pub fn open_blocking_with_result<E, C>(
+        mut settings: Settings<A::Flags>,
+    ) -> (Option<A>, Vec<A::Output>)
     where
         E: iced_runtime::futures::Executor + 'static,
         C: iced_graphics::Compositor<Renderer = A::Renderer, Settings = crate::renderer::Settings>
             + 'static,
     {
+        settings.window.size = settings
+            .iced_baseview
+            .size_constraints
+            .constrain(settings.window.size);
+
+        if settings.iced_baseview.manual_pump {
+            log::warn!("manual_pump is ignored for blocking windows, since nothing can pump them");
+            settings.iced_baseview.manual_pump = false;
+        }
+
         let (sender, receiver) = mpsc::unbounded();
+        let shared = WindowShared::new(Self::initial_window_info(&settings.window));
+        let shared_clone = shared.clone();
+        let exit_state = Arc::new(Mutex::new(None));
+        let exit_state_clone = exit_state.clone();
 
         Window::open_blocking(
             Self::clone_window_options(&settings.window),
             move |window: &mut baseview::Window<'_>| -> IcedWindow<A> {
-                run::<A, E, C>(window, settings, sender, receiver).expect("Launch window")
+                let mut iced_window =
+                    run::<A, E, C>(window, settings, sender, receiver, shared_clone)
+                        .expect("Launch window");
+                iced_window.exit_state = Some(exit_state_clone);
+
+                iced_window
             },
         );
+
+        let application = exit_state
+            .lock()
+            .ok()
+            .and_then(|mut exit_state| exit_state.take());
+
+        (application, shared.outputs.take_all())
     }
 
     /// Open a new child window.
@@ -74,70 +167,206 @@ where
     /// * `settings` - The settings of the window.
     pub fn open_parented<E, C, P>(
         parent: &P,
-        #[allow(unused_mut)] mut settings: Settings<A::Flags>,
-    ) -> WindowHandle<A::Message>
+        mut settings: Settings<A::Flags>,
+    ) -> WindowHandle<A::Message, A::Output>
     where
         E: iced_runtime::futures::Executor + 'static,
         C: iced_graphics::Compositor<Renderer = A::Renderer, Settings = crate::renderer::Settings>
             + 'static,
         P: HasRawWindowHandle,
     {
+        settings.window.size = settings
+            .iced_baseview
+            .size_constraints
+            .constrain(settings.window.size);
+        let size_constraints = settings.iced_baseview.size_constraints;
+
         let (sender, receiver) = mpsc::unbounded();
         let sender_clone = sender.clone();
+        let shared = WindowShared::new(Self::initial_window_info(&settings.window));
+        let shared_clone = shared.clone();
 
         let bv_handle = Window::open_parented(
             parent,
             Self::clone_window_options(&settings.window),
-            move |window: &mut baseview::Window<'_>| -> IcedWindow<A> {
-                run::<A, E, C>(window, settings, sender_clone, receiver).expect("Launch window")
+            move |window: &mut baseview::Window<'_>| -> PumpableWindow<A> {
+                PumpableWindow::new(
+                    run::<A, E, C>(window, settings, sender_clone, receiver, shared_clone)
+                        .expect("Launch window"),
+                )
             },
         );
 
-        WindowHandle::new(bv_handle, sender)
+        WindowHandle::new(bv_handle, sender, shared, size_constraints)
     }
-}
 
-impl<A> WindowHandler for IcedWindow<A>
-where
-    A: Application + Send + 'static,
-    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet,
-{
-    fn on_frame(&mut self, window: &mut Window<'_>) {
+    /// The [`WindowInfo`] to report until the window has been created.
+    fn initial_window_info(window: &WindowOpenOptions) -> WindowInfo {
+        let scale = match window.scale {
+            baseview::WindowScalePolicy::ScaleFactor(scale) => scale,
+            baseview::WindowScalePolicy::SystemScaleFactor => 1.0,
+        };
+
+        WindowInfo::from_logical_size(window.size, scale)
+    }
+
+    /// Do the work of a single frame: apply everything the runtime has produced since the last
+    /// frame, and then update and redraw the user interface if needed. The requests the
+    /// application has made to the `baseview` window are handled separately, since a
+    /// [`WindowHandle::pump`] has no access to the window.
+    fn frame(&mut self) {
         if self.processed_close_signal {
             return;
         }
 
+        // Nothing has happened since the last frame, so there is nothing to update or draw. The
+        // flags are cleared before polling, so anything waking the window in the meantime will
+        // cause the next frame to be processed.
+        let runtime_dirty = self.waker.take_dirty();
+        let instance_dirty = self.instance_waker.take_dirty();
+        if !(runtime_dirty || instance_dirty) {
+            return;
+        }
+
         // Flush all messages. This will block until the instance is finished.
-        let _ = self.instance.as_mut().poll(&mut self.runtime_context);
+        self.poll_instance();
 
-        // Poll subscriptions and send the corresponding messages.
-        while let Ok(Some(message)) = self.runtime_rx.try_next() {
-            self.sender
-                .start_send(RuntimeEvent::UserEvent(message))
-                .expect("Send event");
-        }
+        // Make progress on futures running on the GUI thread.
+        self.poll_local();
+
+        // Poll commands and subscriptions and send the corresponding messages.
+        self.poll_runtime();
+
+        // Sending events to the instance wakes it, but everything sent so far is handled by the
+        // poll below, so that is no reason to process the next frame. Only the instance asking for
+        // another frame, or something arriving after this point, marks the window dirty again.
+        let _ = self.instance_waker.take_dirty();
 
         // Send the event to the instance.
-        self.sender
-            .start_send(RuntimeEvent::MainEventsCleared)
-            .expect("Send event");
+        self.send(RuntimeEvent::MainEventsCleared);
 
         // Send event to render the frame.
-        self.sender
-            .start_send(RuntimeEvent::RedrawRequested)
-            .expect("Send event");
+        self.send(RuntimeEvent::RedrawRequested);
 
         // Flush all messages. This will block until the instance is finished.
-        let _ = self.instance.as_mut().poll(&mut self.runtime_context);
+        self.poll_instance();
+    }
 
+    /// Handle all requests the application has made to the `baseview` window.
+    fn process_window_queue(&mut self, window: &mut Window<'_>) {
         while let Ok(Some(msg)) = self.window_queue_rx.try_next() {
             match msg {
                 WindowQueueMessage::CloseWindow => {
                     window.close();
                 }
+                WindowQueueMessage::Resize(size) => {
+                    window.resize(size);
+                }
+            }
+        }
+    }
+
+    /// Forward all messages the runtime has produced to the instance. This registers the waker
+    /// with the runtime, so the window is marked as dirty once a command or subscription produces
+    /// a new message.
+    fn poll_runtime(&mut self) {
+        let waker = Waker::from(self.waker.clone());
+        let mut context = Context::from_waker(&waker);
+
+        while let Poll::Ready(Some(message)) = self.runtime_rx.poll_next_unpin(&mut context) {
+            self.send(RuntimeEvent::UserEvent(message));
+        }
+    }
+
+    /// Send an event to the instance. Once the instance has finished or panicked, it no longer
+    /// receives events, and they are dropped.
+    fn send(&self, event: RuntimeEvent<A::Message>) {
+        let _ = self.sender.unbounded_send(event);
+    }
+
+    /// Poll the futures running on the GUI thread, see [`LocalExecutor`].
+    ///
+    /// [`LocalExecutor`]: crate::executor::LocalExecutor
+    fn poll_local(&mut self) {
+        crate::executor::poll_local();
+    }
+
+    /// Poll the instance, flushing all messages. Once the instance has finished, the final state
+    /// of the application is handed over to [`exit_state`](Self::exit_state).
+    fn poll_instance(&mut self) {
+        let waker = Waker::from(self.instance_waker.clone());
+        let mut context = Context::from_waker(&waker);
+
+        // Panics in the application's `update` and `view` are already caught by the instance.
+        // Anything else, like a panic in a widget, must not unwind into the host either. The
+        // instance cannot be resumed after that, so the window stops responding instead.
+        //
+        // Unwinding out of the instance drops everything it owns. The graphics are torn down in
+        // the same order as when the window closes normally, see `application::Graphics`.
+        let poll = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.instance.as_mut().poll(&mut context)
+        }));
+
+        let poll = match poll {
+            Ok(poll) => poll,
+            Err(payload) => {
+                let message = crate::crash::panic_message(payload.as_ref());
+                log::error!("the window panicked: {}", message);
+
+                if let Some(panic_hook) = &self.panic_hook {
+                    panic_hook(&message);
+                }
+
+                // Release what is left of the instance right away, instead of when the window
+                // closes, and ignore everything that happens to the window from now on
+                self.instance = Box::pin(futures::future::pending());
+                self.processed_close_signal = true;
+
+                return;
+            }
+        };
+
+        if let Poll::Ready(application) = poll {
+            if let Some(exit_state) = &self.exit_state {
+                if let Ok(mut exit_state) = exit_state.lock() {
+                    *exit_state = Some(application);
+                }
             }
         }
     }
+}
+
+impl<A> Drop for IcedWindow<A>
+where
+    A: Application + Send + 'static,
+    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet,
+{
+    fn drop(&mut self) {
+        // Release the instance first, so the application and all of its resources are gone by the
+        // time anyone waiting for the window is notified.
+        self.instance = Box::pin(futures::future::pending());
+        crate::executor::release_local(&self.waker);
+
+        // The completion hold is released after this, along with the other fields
+    }
+}
+
+impl<A> WindowHandler for IcedWindow<A>
+where
+    A: Application + Send + 'static,
+    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet,
+{
+    fn on_frame(&mut self, window: &mut Window<'_>) {
+        // With a manual pump, the host decides when the window does its work.
+        if !self.manual_pump || self.shared.pump_requested.swap(false, Ordering::AcqRel) {
+            self.frame();
+        }
+
+        // This includes the requests made while the window was pumped
+        if !self.processed_close_signal {
+            self.process_window_queue(window);
+        }
+    }
 
     fn on_event(&mut self, window: &mut Window<'_>, event: Event) -> EventStatus {
         if self.processed_close_signal {
@@ -147,78 +376,192 @@ where
         let status = if requests_exit(&event) {
             self.processed_close_signal = true;
 
-            self.sender
-                .start_send(RuntimeEvent::WillClose)
-                .expect("Send event");
+            self.send(RuntimeEvent::WillClose);
 
             // Flush all messages so the application receives the close event. This will block until the instance is finished.
-            let _ = self.instance.as_mut().poll(&mut self.runtime_context);
+            self.poll_instance();
 
             EventStatus::Ignored
         } else {
             // Send the event to the instance.
-            self.sender
-                .start_send(RuntimeEvent::Baseview((event, true)))
-                .expect("Send event");
+            self.send(RuntimeEvent::Baseview((event, true)));
+
+            // The user interface is updated and redrawn on the next frame.
+            self.waker.wake_by_ref();
 
             // Flush all messages so the application receives the event. This will block until the instance is finished.
-            let _ = self.instance.as_mut().poll(&mut self.runtime_context);
+            self.poll_instance();
 
             // TODO: make this Copy
             *self.event_status.borrow()
         };
 
         if !self.processed_close_signal {
-            while let Ok(Some(msg)) = self.window_queue_rx.try_next() {
-                match msg {
-                    WindowQueueMessage::CloseWindow => {
-                        window.close();
-                    }
-                }
-            }
+            self.process_window_queue(window);
         }
 
         status
     }
 }
 
-/// Returns true if the provided event should cause an [`Application`] to
-/// exit.
-pub fn requests_exit(event: &baseview::Event) -> bool {
-    match event {
-        baseview::Event::Window(baseview::WindowEvent::WillClose) => true,
-        #[cfg(target_os = "macos")]
-        baseview::Event::Keyboard(event) => {
-            if event.code == keyboard_types::Code::KeyQ
-                && event.modifiers == keyboard_types::Modifiers::META
-                && event.state == keyboard_types::KeyState::Down
-            {
-                return true;
-            }
+/// Does the work of a frame that has been requested through [`WindowHandle::pump`].
+trait Pump {
+    fn pump(&mut self);
+}
 
-            false
+impl<A> Pump for IcedWindow<A>
+where
+    A: Application + Send + 'static,
+    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet,
+{
+    fn pump(&mut self) {
+        if self.manual_pump && self.shared.pump_requested.swap(false, Ordering::AcqRel) {
+            self.frame();
         }
-        _ => false,
     }
 }
 
+thread_local! {
+    /// The windows running on this thread that can be pumped, by the address of their
+    /// [`WindowShared`].
+    static PUMPABLE_WINDOWS: RefCell<Vec<(usize, Weak<RefCell<dyn Pump>>)>> =
+        RefCell::new(Vec::new());
+}
+
+/// A window opened with `open_parented`, which [`WindowHandle::pump`] can reach when it is called
+/// on the thread the window runs on.
+struct PumpableWindow<A>(Rc<RefCell<IcedWindow<A>>>)
+where
+    A: Application + Send + 'static,
+    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet;
+
+impl<A> PumpableWindow<A>
+where
+    A: Application + Send + 'static,
+    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet,
+{
+    fn new(window: IcedWindow<A>) -> Self {
+        let key = shared_key(&window.shared);
+        let window = Rc::new(RefCell::new(window));
+        let pump: Rc<RefCell<dyn Pump>> = window.clone();
+
+        PUMPABLE_WINDOWS.with(|windows| {
+            let mut windows = windows.borrow_mut();
+
+            // Forget about windows that have been closed.
+            windows.retain(|(_, window)| window.strong_count() > 0);
+            windows.push((key, Rc::downgrade(&pump)));
+        });
+
+        Self(window)
+    }
+}
+
+impl<A> WindowHandler for PumpableWindow<A>
+where
+    A: Application + Send + 'static,
+    <A::Renderer as iced_runtime::core::Renderer>::Theme: StyleSheet,
+{
+    // The window is only borrowed elsewhere while it is being pumped, which `baseview` may
+    // interrupt on some platforms, e.g. to deliver a resize while presenting. The frame is skipped
+    // and the event is ignored in that case.
+
+    fn on_frame(&mut self, window: &mut Window<'_>) {
+        if let Ok(mut iced_window) = self.0.try_borrow_mut() {
+            iced_window.on_frame(window);
+        }
+    }
+
+    fn on_event(&mut self, window: &mut Window<'_>, event: Event) -> EventStatus {
+        match self.0.try_borrow_mut() {
+            Ok(mut iced_window) => iced_window.on_event(window, event),
+            Err(_) => EventStatus::Ignored,
+        }
+    }
+}
+
+/// Identifies a window by its [`WindowShared`], which lives as long as the window.
+fn shared_key<Output>(shared: &Arc<WindowShared<Output>>) -> usize {
+    Arc::as_ptr(shared).cast::<()>() as usize
+}
+
+/// Keeps track of whether the window has any work to do on its next frame.
+///
+/// A window has one of these for its instance, which is woken when the instance needs another
+/// frame or a message has been sent to it, and one that is woken by the runtime when a command or
+/// subscription has produced a message and by the window itself when it receives an event. Frames
+/// in which the window is not dirty are skipped entirely.
+///
+/// Baseview currently has no way to wake its event loop from another thread, so waking only marks
+/// the window as dirty and the work is picked up on the next frame.
+#[derive(Debug)]
+pub struct RuntimeWaker {
+    dirty: AtomicBool,
+}
+
+impl RuntimeWaker {
+    /// Creates a new [`RuntimeWaker`]. The window starts out dirty so the first frame is drawn.
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            dirty: AtomicBool::new(true),
+        })
+    }
+
+    /// Returns whether the window is dirty, and clears the flag.
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Wake for RuntimeWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.dirty.store(true, Ordering::Release);
+    }
+}
+
+/// Returns true if the provided event should cause an [`Application`] to
+/// exit.
+///
+/// Only `WindowEvent::WillClose` does, since `baseview` closes the window right after it. Exit
+/// shortcuts first go through the application, see
+/// [`exit_shortcuts`](crate::settings::IcedBaseviewSettings::exit_shortcuts).
+pub fn requests_exit(event: &baseview::Event) -> bool {
+    matches!(
+        event,
+        baseview::Event::Window(baseview::WindowEvent::WillClose)
+    )
+}
+
 /// Use this to send custom events to the iced window.
 ///
 /// Please note this channel is ***not*** realtime-safe and should never be
 /// be used to send events from the audio thread. Use a realtime-safe ring
 /// buffer instead.
 #[allow(missing_debug_implementations)]
-pub struct WindowHandle<Message: 'static + Send> {
+pub struct WindowHandle<Message: 'static + Send, Output: 'static + Send = ()> {
     bv_handle: baseview::WindowHandle,
     tx: mpsc::UnboundedSender<RuntimeEvent<Message>>,
+    shared: Arc<WindowShared<Output>>,
+    size_constraints: SizeConstraints,
 }
 
-impl<Message: 'static + Send> WindowHandle<Message> {
+impl<Message: 'static + Send, Output: 'static + Send> WindowHandle<Message, Output> {
     pub(crate) fn new(
         bv_handle: baseview::WindowHandle,
         tx: mpsc::UnboundedSender<RuntimeEvent<Message>>,
+        shared: Arc<WindowShared<Output>>,
+        size_constraints: SizeConstraints,
     ) -> Self {
-        Self { bv_handle, tx }
+        Self {
+            bv_handle,
+            tx,
+            shared,
+            size_constraints,
+        }
     }
 
     /// Send a custom `baseview::Event` to the window.
@@ -239,6 +582,149 @@ impl<Message: 'static + Send> WindowHandle<Message> {
         self.tx.start_send(RuntimeEvent::UserEvent(msg))
     }
 
+    /// Returns a [`MessageSender`] that can send messages to the window from any thread, without
+    /// needing access to the [`WindowHandle`].
+    pub fn sender(&self) -> MessageSender<Message> {
+        MessageSender {
+            tx: self.tx.clone(),
+        }
+    }
+
+    /// Returns the next output the application has emitted with [`emit`], if any.
+    ///
+    /// Outputs are only queued while no callback has been registered with
+    /// [`on_output`](Self::on_output).
+    pub fn try_recv_output(&self) -> Option<Output> {
+        self.shared.outputs.pop()
+    }
+
+    /// Register a callback that receives every output the application emits with [`emit`],
+    /// replacing any previously registered callback. Outputs that have been queued before are
+    /// passed to the callback right away, on the calling thread.
+    ///
+    /// After that, the callback is called on the thread the window runs on, while the application
+    /// is being updated.
+    pub fn on_output(&self, callback: impl FnMut(Output) + Send + 'static) {
+        self.shared.outputs.set_callback(Box::new(callback));
+    }
+
+    /// Resize the window to the given logical size. This is meant to be used when the host
+    /// resizes the editor, e.g. from VST3's `onSize` or CLAP's `gui.set_size`.
+    ///
+    /// The size is adjusted to fit the window's [`SizeConstraints`]. The window's viewport is
+    /// updated the same way as when `baseview` reports a resize.
+    pub fn resize(&self, size: baseview::Size) -> Result<(), WindowClosed> {
+        self.tx
+            .unbounded_send(RuntimeEvent::Resize(size))
+            .map_err(|_| WindowClosed)
+    }
+
+    /// Set the scale factor the window should use in place of the system scale factor. This is
+    /// meant to be used when the host tells the editor about its content scale, e.g. from CLAP's
+    /// `gui.set_scale`.
+    ///
+    /// The logical size of the window is kept, so the window's physical size will change. Scale
+    /// factors that are not finite and positive are rejected.
+    pub fn set_scale_factor(&self, scale_factor: f64) -> Result<(), ScaleFactorError> {
+        if !(scale_factor.is_finite() && scale_factor > 0.0) {
+            return Err(ScaleFactorError::Invalid(scale_factor));
+        }
+
+        self.tx
+            .unbounded_send(RuntimeEvent::SetScaleFactor(scale_factor))
+            .map_err(|_| WindowClosed.into())
+    }
+
+    /// Forward a text composition from an input method to the window, e.g. to let users in Japan,
+    /// China or Korea type into text inputs.
+    ///
+    /// This is the only way compositions reach the window. Neither `baseview` nor this crate talk
+    /// to the platform's input method, so there is no XIM, IMM32 or `NSTextInputClient` support.
+    /// The owner of the window has to hook into the input method itself, or provide its own text
+    /// entry, and call this for every change to the composition.
+    ///
+    /// The application is notified through [`WindowSubs::on_composition`]. Committed text is typed
+    /// into the focused widget like regular characters, but the text being composed (the
+    /// preedit) is not shown by iced's text inputs. Applications that want to show it have to
+    /// draw it themselves from `on_composition`.
+    pub fn send_composition(&self, composition: Composition) -> Result<(), WindowClosed> {
+        self.tx
+            .unbounded_send(RuntimeEvent::Composition(composition))
+            .map_err(|_| WindowClosed)
+    }
+
+    /// Returns the area of the text cursor the application has reported with
+    /// [`set_ime_cursor_area`], in logical window coordinates. The input method's candidate
+    /// window should be placed next to it.
+    pub fn ime_cursor_area(&self) -> Option<Rectangle> {
+        match self.shared.ime_cursor_area.lock() {
+            Ok(area) => *area,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    /// Returns the current logical size of the window.
+    pub fn size(&self) -> baseview::Size {
+        self.window_info().logical_size()
+    }
+
+    /// Returns the current scale factor of the window.
+    pub fn scale_factor(&self) -> f64 {
+        self.window_info().scale()
+    }
+
+    /// Returns the current size and scale factor of the window.
+    pub fn window_info(&self) -> WindowInfo {
+        match self.shared.window_info.lock() {
+            Ok(window_info) => *window_info,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    /// Returns the constraints on the window's size.
+    pub fn size_constraints(&self) -> SizeConstraints {
+        self.size_constraints
+    }
+
+    /// Returns `true` if the window may be resized.
+    pub fn can_resize(&self) -> bool {
+        self.size_constraints.can_resize()
+    }
+
+    /// Returns the size the window would end up with if it were resized to `size`.
+    pub fn check_size(&self, size: baseview::Size) -> baseview::Size {
+        self.size_constraints.check_size(size, self.size())
+    }
+
+    /// Let the window do the work of a single frame. This is meant to be called from the host's
+    /// timer, e.g. CLAP's `timer-support` or VST3's `IRunLoop`, when
+    /// [`manual_pump`](crate::settings::IcedBaseviewSettings::manual_pump) is enabled.
+    ///
+    /// When called on the thread the window runs on, the work is done right away, and the
+    /// requests the application makes to the window, like resizing it, on the window's next frame
+    /// callback. Otherwise, e.g. for `baseview`'s X11 windows, which run on a thread of their own,
+    /// all of the work is done on the window's next frame callback. Without a manual pump, this
+    /// does nothing.
+    pub fn pump(&self) {
+        self.shared.pump_requested.store(true, Ordering::Release);
+
+        let key = shared_key(&self.shared);
+        let window = PUMPABLE_WINDOWS.with(|windows| {
+            windows
+                .borrow()
+                .iter()
+                .filter(|(window_key, _)| *window_key == key)
+                .find_map(|(_, window)| window.upgrade())
+        });
+
+        // The window may be pumped from within its own frame, which then does the work instead
+        if let Some(window) = window {
+            if let Ok(mut window) = window.try_borrow_mut() {
+                window.pump();
+            }
+        }
+    }
+
     /// Signal the window to close.
     pub fn close_window(&mut self) {
         self.bv_handle.close();
@@ -249,9 +735,225 @@ impl<Message: 'static + Send> WindowHandle<Message> {
     pub fn is_open(&self) -> bool {
         self.bv_handle.is_open()
     }
+
+    /// Returns `true` once the window has closed and the application has fully shut down. By then,
+    /// the application and all of its resources have been released, its futures have stopped
+    /// running, and its [`cleanup`](crate::Application::cleanup) future has completed or timed out.
+    pub fn is_finished(&self) -> bool {
+        self.shared.completion.is_finished()
+    }
+
+    /// Register a callback that is called once the window has closed and the application has
+    /// fully shut down. If that has already happened, the callback is called immediately.
+    ///
+    /// The callback is called on the thread that finishes the shutdown, e.g. the thread the window
+    /// runs on or the thread running the cleanup future.
+    pub fn on_closed(&self, callback: impl FnOnce() + Send + 'static) {
+        self.shared.completion.on_finished(Box::new(callback));
+    }
+
+    /// Block the current thread until the window has closed and the application has fully shut
+    /// down. Plugin wrappers can use this to only free state shared with the editor once it is
+    /// truly gone.
+    ///
+    /// This must not be called from the thread the window runs on, since that would deadlock.
+    pub fn join(&self) {
+        self.shared.completion.wait();
+    }
+
+    /// Returns a future that completes once the window has closed and the application has fully
+    /// shut down.
+    pub fn join_async(&self) -> impl futures::Future<Output = ()> + Send + 'static {
+        let completion = self.shared.completion.clone();
+
+        futures::future::poll_fn(move |context| completion.poll_finished(context))
+    }
 }
 
-unsafe impl<Message: 'static + Send> HasRawWindowHandle for WindowHandle<Message> {
+/// The state a window shares with its [`WindowHandle`].
+pub(crate) struct WindowShared<Output> {
+    /// The current size and scale factor of the window.
+    pub(crate) window_info: Mutex<WindowInfo>,
+    /// The area of the text cursor, in logical window coordinates.
+    pub(crate) ime_cursor_area: Mutex<Option<Rectangle>>,
+    /// Set by [`WindowHandle::pump`], and cleared once the window has done its work.
+    pub(crate) pump_requested: AtomicBool,
+    pub(crate) completion: Arc<Completion>,
+    pub(crate) outputs: OutputSink<Output>,
+}
+
+impl<Output> WindowShared<Output> {
+    pub(crate) fn new(window_info: WindowInfo) -> Arc<Self> {
+        Arc::new(Self {
+            window_info: Mutex::new(window_info),
+            ime_cursor_area: Mutex::new(None),
+            pump_requested: AtomicBool::new(false),
+            completion: Arc::default(),
+            outputs: OutputSink::default(),
+        })
+    }
+}
+
+/// Keeps track of whether a window has finished, i.e. its application has shut down and released
+/// all of its resources. The window finishes once every [`CompletionHold`] on it has been dropped.
+#[derive(Default)]
+pub(crate) struct Completion {
+    state: Mutex<CompletionState>,
+    finished: Condvar,
+}
+
+#[derive(Default)]
+struct CompletionState {
+    holds: usize,
+    finished: bool,
+    callbacks: Vec<Box<dyn FnOnce() + Send>>,
+    wakers: Vec<Waker>,
+}
+
+impl Completion {
+    fn lock(&self) -> std::sync::MutexGuard<'_, CompletionState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Keeps the window from finishing until the returned hold has been dropped.
+    pub(crate) fn hold(self: &Arc<Self>) -> CompletionHold {
+        self.lock().holds += 1;
+
+        CompletionHold(self.clone())
+    }
+
+    /// Marks the window as finished once the last hold has been released, and notifies everyone
+    /// waiting for that.
+    fn release(&self) {
+        let (callbacks, wakers) = {
+            let mut state = self.lock();
+            state.holds -= 1;
+            if state.holds > 0 {
+                return;
+            }
+
+            state.finished = true;
+
+            (
+                std::mem::take(&mut state.callbacks),
+                std::mem::take(&mut state.wakers),
+            )
+        };
+
+        self.finished.notify_all();
+
+        for callback in callbacks {
+            callback();
+        }
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.lock().finished
+    }
+
+    fn on_finished(&self, callback: Box<dyn FnOnce() + Send>) {
+        let mut state = self.lock();
+
+        if state.finished {
+            drop(state);
+            callback();
+        } else {
+            state.callbacks.push(callback);
+        }
+    }
+
+    fn wait(&self) {
+        let mut state = self.lock();
+
+        while !state.finished {
+            state = self
+                .finished
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    fn poll_finished(&self, context: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.lock();
+
+        if state.finished {
+            Poll::Ready(())
+        } else {
+            if !state
+                .wakers
+                .iter()
+                .any(|waker| waker.will_wake(context.waker()))
+            {
+                state.wakers.push(context.waker().clone());
+            }
+
+            Poll::Pending
+        }
+    }
+}
+
+/// Keeps a window from finishing while part of it is still shutting down, see
+/// [`Completion::hold`].
+pub(crate) struct CompletionHold(Arc<Completion>);
+
+impl Drop for CompletionHold {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+impl std::fmt::Debug for CompletionHold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CompletionHold").finish()
+    }
+}
+
+/// Sends messages to a window. This can be cloned cheaply and shared between threads.
+///
+/// Please note this channel is ***not*** realtime-safe and should never be
+/// used to send messages from the audio thread. Use a realtime-safe ring
+/// buffer instead.
+pub struct MessageSender<Message: 'static + Send> {
+    tx: mpsc::UnboundedSender<RuntimeEvent<Message>>,
+}
+
+impl<Message: 'static + Send> MessageSender<Message> {
+    /// Send a custom message to the window.
+    pub fn send(&self, message: Message) -> Result<(), WindowClosed> {
+        self.tx
+            .unbounded_send(RuntimeEvent::UserEvent(message))
+            .map_err(|_| WindowClosed)
+    }
+
+    /// Returns `true` if the window has been closed, and messages can no longer be sent to it.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+impl<Message: 'static + Send> Clone for MessageSender<Message> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<Message: 'static + Send> std::fmt::Debug for MessageSender<Message> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageSender")
+            .field("is_closed", &self.is_closed())
+            .finish()
+    }
+}
+
+unsafe impl<Message: 'static + Send, Output: 'static + Send> HasRawWindowHandle
+    for WindowHandle<Message, Output>
+{
     fn raw_window_handle(&self) -> RawWindowHandle {
         self.bv_handle.raw_window_handle()
     }
@@ -260,24 +962,377 @@ unsafe impl<Message: 'static + Send> HasRawWindowHandle for WindowHandle<Message
 #[derive(Debug, Clone, Copy)]
 pub enum WindowQueueMessage {
     CloseWindow,
+    Resize(baseview::Size),
 }
 
 /// Used to request things from the `baseview` window.
 pub struct WindowQueue {
     tx: mpsc::UnboundedSender<WindowQueueMessage>,
+    requests_tx: mpsc::UnboundedSender<WindowRequest>,
+    requests_rx: mpsc::UnboundedReceiver<WindowRequest>,
 }
 
 impl WindowQueue {
     pub fn new() -> (Self, mpsc::UnboundedReceiver<WindowQueueMessage>) {
         let (tx, rx) = mpsc::unbounded();
+        let (requests_tx, requests_rx) = mpsc::unbounded();
 
-        (Self { tx }, rx)
+        (
+            Self {
+                tx,
+                requests_tx,
+                requests_rx,
+            },
+            rx,
+        )
     }
 
     /// Quit the current application and close the window.
     pub fn close_window(&mut self) -> Result<(), SendError> {
         self.tx.start_send(WindowQueueMessage::CloseWindow)
     }
+
+    /// Resize the window to the given logical size.
+    pub fn resize(&mut self, size: baseview::Size) -> Result<(), SendError> {
+        self.tx.start_send(WindowQueueMessage::Resize(size))
+    }
+
+    /// Queue a request to the window, as if the application had made it through a [`Command`].
+    pub(crate) fn request(&mut self, request: WindowRequest) {
+        let _ = self.requests_tx.unbounded_send(request);
+    }
+
+    /// Take all requests that have been made to the window.
+    pub(crate) fn take_requests(&mut self) -> Vec<WindowRequest> {
+        let mut requests = Vec::new();
+        while let Ok(Some(request)) = self.requests_rx.try_next() {
+            requests.push(request);
+        }
+
+        requests
+    }
+
+    /// Returns the [`WindowContext`] through which the application reaches this window, the
+    /// owner of the window through `outputs`, and the window's futures through `tasks`.
+    pub(crate) fn context<Output: 'static>(
+        &self,
+        outputs: &OutputSink<Output>,
+        tasks: &TrackedTasks,
+    ) -> WindowContext {
+        WindowContext {
+            requests: self.requests_tx.clone(),
+            outputs: Rc::new(outputs.clone()),
+            tasks: tasks.clone(),
+        }
+    }
+}
+
+/// The parts of a window the application can reach while it is being initialized or updated, see
+/// [`WindowContext::enter`].
+#[derive(Clone)]
+pub(crate) struct WindowContext {
+    requests: mpsc::UnboundedSender<WindowRequest>,
+    /// The [`OutputSink`] of the application's `Output` type.
+    outputs: Rc<dyn Any>,
+    /// The futures the window has spawned, which are aborted once it closes.
+    pub(crate) tasks: TrackedTasks,
+}
+
+thread_local! {
+    /// The window whose application is currently being initialized or updated on this thread.
+    static CURRENT_WINDOW: RefCell<Option<WindowContext>> = RefCell::new(None);
+}
+
+impl WindowContext {
+    /// Runs `f` with this window as the target of window commands like [`set_zoom`]. Windows may
+    /// be opened from within `f`, so the previous target is restored afterwards, also when `f`
+    /// panics.
+    pub(crate) fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<WindowContext>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT_WINDOW.with(|current| *current.borrow_mut() = previous);
+            }
+        }
+
+        let previous = CURRENT_WINDOW.with(|current| current.replace(Some(self.clone())));
+        let _restore = Restore(previous);
+
+        f()
+    }
+
+    /// Returns the window whose application is currently being initialized or updated, if any.
+    pub(crate) fn current() -> Option<WindowContext> {
+        CURRENT_WINDOW.with(|current| current.borrow().clone())
+    }
+}
+
+/// The amount [`zoom_in`] and [`zoom_out`] change the zoom factor by.
+pub const ZOOM_STEP: f64 = 0.1;
+/// The smallest zoom factor.
+pub const MIN_ZOOM: f64 = 0.25;
+/// The largest zoom factor.
+pub const MAX_ZOOM: f64 = 4.0;
+
+/// Close the window. This can be used to confirm a close request, see
+/// [`WindowSubs::on_close_requested`].
+pub fn close<Message>() -> Command<Message> {
+    iced_runtime::window::close()
+}
+
+/// Emit an output to the owner of the window's [`WindowHandle`], e.g. to ask the plugin to load a
+/// preset. This is meant to be called from [`Application::update`], as in
+/// `window::emit::<Self>(output)`.
+///
+/// The output is passed on right away, so the callback registered with
+/// [`WindowHandle::on_output`] runs before `update` returns. Outputs of windows opened with
+/// `open_blocking` are discarded, while `open_blocking_with_result` returns them once the window
+/// has closed.
+///
+/// [`Application::update`]: crate::Application::update
+pub fn emit<A: crate::Application>(output: A::Output) -> Command<A::Message> {
+    let outputs = WindowContext::current()
+        .and_then(|context| context.outputs.downcast::<OutputSink<A::Output>>().ok());
+
+    match outputs {
+        Some(outputs) => outputs.emit(output),
+        None => log::error!(
+            "an output was emitted outside of updating an application of type {}",
+            std::any::type_name::<A>()
+        ),
+    }
+
+    Command::none()
+}
+
+/// Delivers the outputs of an application to the owner of its [`WindowHandle`].
+pub(crate) struct OutputSink<Output>(Arc<Mutex<OutputState<Output>>>);
+
+struct OutputState<Output> {
+    queue: VecDeque<Output>,
+    callback: Option<Box<dyn FnMut(Output) + Send>>,
+}
+
+impl<Output> OutputSink<Output> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, OutputState<Output>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Passes the output to the registered callback, or queues it if there is none.
+    pub(crate) fn emit(&self, output: Output) {
+        self.lock().queue.push_back(output);
+        self.deliver();
+    }
+
+    /// Passes the queued outputs to the registered callback, in order. The callback is called
+    /// without holding the lock, so it may use the window handle. While it is taken out, outputs
+    /// are queued, and the queue is drained again once it has been put back.
+    fn deliver(&self) {
+        loop {
+            let (mut callback, output) = {
+                let mut state = self.lock();
+                if state.queue.is_empty() {
+                    return;
+                }
+
+                // Either no callback has been registered, or the outputs are being delivered
+                // already
+                let Some(callback) = state.callback.take() else {
+                    return;
+                };
+                let output = state.queue.pop_front().expect("The queue is not empty");
+
+                (callback, output)
+            };
+
+            callback(output);
+
+            // The callback may have been replaced in the meantime
+            let mut state = self.lock();
+            if state.callback.is_none() {
+                state.callback = Some(callback);
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<Output> {
+        self.lock().queue.pop_front()
+    }
+
+    /// Takes all outputs that have been queued so far.
+    fn take_all(&self) -> Vec<Output> {
+        self.lock().queue.drain(..).collect()
+    }
+
+    fn set_callback(&self, callback: Box<dyn FnMut(Output) + Send>) {
+        self.lock().callback = Some(callback);
+        self.deliver();
+    }
+}
+
+impl<Output> Clone for OutputSink<Output> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<Output> Default for OutputSink<Output> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(OutputState {
+            queue: VecDeque::new(),
+            callback: None,
+        })))
+    }
+}
+
+/// Set the zoom factor of the window. The zoom factor multiplies the system scale factor when
+/// the application uses `WindowScalePolicy::SystemScaleFactor`, and is ignored otherwise.
+///
+/// The window is resized so the logical size of its contents stays the same. The new zoom factor
+/// is reported through [`WindowSubs::on_zoom_changed`].
+///
+/// Like the other window commands, this has to be called while the application is being
+/// initialized or updated. The request is passed to the window right away, and the returned
+/// [`Command`] does nothing by itself.
+pub fn set_zoom<Message>(zoom: f64) -> Command<Message> {
+    request(WindowRequest::Zoom(ZoomRequest::Set(zoom)))
+}
+
+/// Increase the zoom factor of the window by [`ZOOM_STEP`]. See [`set_zoom`].
+pub fn zoom_in<Message>() -> Command<Message> {
+    request(WindowRequest::Zoom(ZoomRequest::In))
+}
+
+/// Decrease the zoom factor of the window by [`ZOOM_STEP`]. See [`set_zoom`].
+pub fn zoom_out<Message>() -> Command<Message> {
+    request(WindowRequest::Zoom(ZoomRequest::Out))
+}
+
+/// Reset the zoom factor of the window to `1.0`. See [`set_zoom`].
+pub fn reset_zoom<Message>() -> Command<Message> {
+    request(WindowRequest::Zoom(ZoomRequest::Reset))
+}
+
+/// Report the area of the focused text input's cursor, in the logical coordinates of the user
+/// interface, or `None` once no text input is focused. The owner of the window reads it with
+/// [`WindowHandle::ime_cursor_area`] to place the input method's candidate window.
+///
+/// This has to be called while the application is being initialized or updated, see
+/// [`set_zoom`].
+pub fn set_ime_cursor_area<Message>(area: Option<Rectangle>) -> Command<Message> {
+    request(WindowRequest::SetImeCursorArea(area))
+}
+
+/// A text composition from an input method, see [`WindowHandle::send_composition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Composition {
+    /// The user has started composing text.
+    Started,
+    /// The text being composed has changed. The cursor is the byte range of the selection within
+    /// `text`, if the input method shows one. Empty text ends the composition without committing
+    /// anything.
+    Updated {
+        /// The text being composed.
+        text: String,
+        /// The byte range of the cursor or selection within `text`.
+        cursor: Option<(usize, usize)>,
+    },
+    /// The user has finished composing, and the text should be inserted.
+    Committed(String),
+}
+
+/// A request to the window that cannot be expressed with iced's own [`Command`] actions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum WindowRequest {
+    Zoom(ZoomRequest),
+    SetImeCursorArea(Option<Rectangle>),
+}
+
+/// A change to the zoom factor of the window, see [`set_zoom`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ZoomRequest {
+    Set(f64),
+    In,
+    Out,
+    Reset,
+}
+
+impl ZoomRequest {
+    /// Returns the zoom factor after applying this request to `zoom`, within the allowed range.
+    /// Setting the zoom factor to NaN leaves it unchanged.
+    pub(crate) fn apply(self, zoom: f64) -> f64 {
+        let requested = match self {
+            ZoomRequest::Set(zoom) => zoom,
+            ZoomRequest::In => zoom + ZOOM_STEP,
+            ZoomRequest::Out => zoom - ZOOM_STEP,
+            ZoomRequest::Reset => 1.0,
+        };
+
+        if requested.is_nan() {
+            return zoom;
+        }
+
+        requested.clamp(MIN_ZOOM, MAX_ZOOM)
+    }
+}
+
+impl WindowRequest {
+    /// Returns the request bound to the zoom shortcut in `event`, if any.
+    ///
+    /// The shortcuts follow the keyboard layout, and have to be pressed with Ctrl (Cmd on macOS)
+    /// and no other modifiers, except for Shift where it is needed to type the character.
+    pub(crate) fn from_zoom_shortcut(event: &Event) -> Option<Self> {
+        use keyboard_types::Modifiers;
+
+        #[cfg(target_os = "macos")]
+        let modifier = Modifiers::META;
+        #[cfg(not(target_os = "macos"))]
+        let modifier = Modifiers::CONTROL;
+
+        let event = match event {
+            Event::Keyboard(event) => event,
+            _ => return None,
+        };
+
+        let shortcuts = vec![
+            Shortcut::character(modifier, "=", ZoomRequest::In),
+            Shortcut::character(modifier, "+", ZoomRequest::In),
+            Shortcut::character(modifier, "-", ZoomRequest::Out),
+            Shortcut::character(modifier, "0", ZoomRequest::Reset),
+        ];
+
+        crate::shortcut::find_message(shortcuts, event).map(WindowRequest::Zoom)
+    }
+}
+
+/// Passes a request to the window whose application is currently being initialized or updated.
+fn request<Message>(request: WindowRequest) -> Command<Message> {
+    let sent = WindowContext::current().map(|context| context.requests.unbounded_send(request));
+
+    if sent.is_none() {
+        log::warn!(
+            "{:?} was requested outside of initializing or updating an application",
+            request
+        );
+    }
+
+    Command::none()
+}
+
+/// Timing information about a frame, see [`WindowSubs::on_frame_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// The time the frame started at. All messages for the same frame share this timestamp.
+    pub now: Instant,
+    /// The time that has passed since the previous frame started, or zero for the first frame.
+    pub delta: Duration,
+    /// The number of frames before this one.
+    pub frame_index: u64,
+    /// Whether the previous frame has been presented. This is `false` if nothing had changed, or
+    /// if presenting the frame failed.
+    pub presented_last_frame: bool,
 }
 
 /// This struct creates subscriptions for common window events.
@@ -285,15 +1340,71 @@ impl WindowQueue {
 pub struct WindowSubs<Message> {
     /// The message to send right before each rendering frame.
     pub on_frame: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send right before each rendering frame, with timing information about the
+    /// frame. Use this to make animations independent of the frame rate.
+    pub on_frame_info: Option<Arc<dyn Fn(FrameInfo) -> Option<Message>>>,
     /// The message to send when the window is about to close.
     pub on_window_will_close: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send when the user presses one of the
+    /// [`exit_shortcuts`](crate::settings::IcedBaseviewSettings::exit_shortcuts).
+    ///
+    /// When this is set, the window is not closed. The application can ask the user first, e.g.
+    /// whether to save their changes, and then close the window with [`close`]. The window
+    /// manager's close button cannot be intercepted this way, since `baseview` closes the window
+    /// right after sending `WillClose`.
+    pub on_close_requested: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send when the zoom factor of the window has changed, e.g. to persist it.
+    pub on_zoom_changed: Option<Arc<dyn Fn(f64) -> Option<Message>>>,
+    /// The message to send when the window has been resized, with its new logical size and scale
+    /// factor.
+    pub on_resized: Option<Arc<dyn Fn(baseview::Size, f64) -> Option<Message>>>,
+    /// The message to send when the scale factor of the window has changed.
+    pub on_scale_factor_changed: Option<Arc<dyn Fn(f64) -> Option<Message>>>,
+    /// The message to send when the window has gained focus.
+    pub on_focused: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send when the window has lost focus.
+    pub on_unfocused: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send when the cursor has entered the window.
+    pub on_cursor_entered: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send when the cursor has left the window.
+    pub on_cursor_left: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// The message to send once the first frame has been presented.
+    pub on_first_frame_presented: Option<Arc<dyn Fn() -> Option<Message>>>,
+    /// Receives every `baseview` event before it is converted to iced events. This gives access to
+    /// information iced's events can't express, like physical key codes or the origin of scroll
+    /// deltas.
+    ///
+    /// Returning [`EventStatus::Captured`] marks the event as handled for the host, even if no
+    /// widget captures it. The event is still passed on to the user interface so its state stays
+    /// consistent.
+    pub on_baseview_event: Option<Arc<dyn Fn(&Event) -> (Option<Message>, EventStatus)>>,
+    /// The message to send when a key is pressed or released that has no equivalent iced
+    /// [`KeyCode`](crate::core::keyboard::KeyCode), and thus never produces a `KeyPressed` or
+    /// `KeyReleased` event. The keyboard event carries the raw physical key code.
+    pub on_unmapped_key: Option<Arc<dyn Fn(&keyboard_types::KeyboardEvent) -> Option<Message>>>,
+    /// The message to send when the owner of the window forwards a text composition with
+    /// [`WindowHandle::send_composition`], e.g. to show the text being composed.
+    pub on_composition: Option<Arc<dyn Fn(&Composition) -> Option<Message>>>,
 }
 
 impl<Message> Default for WindowSubs<Message> {
     fn default() -> Self {
         WindowSubs {
             on_frame: None,
+            on_frame_info: None,
             on_window_will_close: None,
+            on_close_requested: None,
+            on_zoom_changed: None,
+            on_resized: None,
+            on_scale_factor_changed: None,
+            on_focused: None,
+            on_unfocused: None,
+            on_cursor_entered: None,
+            on_cursor_left: None,
+            on_first_frame_presented: None,
+            on_baseview_event: None,
+            on_unmapped_key: None,
+            on_composition: None,
         }
     }
 }